@@ -4,14 +4,28 @@ CREATE TABLE poll
     id character varying NOT NULL,
     CONSTRAINT poll_pkey PRIMARY KEY (id),
     name character varying NOT NULL,
-    description character varying NOT NULL,
+    description character varying,
     owner_id character varying NOT NULL,
     expires timestamp with time zone NOT NULL,
     close timestamp with time zone,
-    write_ins boolean NOT NULL
+    write_ins boolean NOT NULL,
+    seats smallint NOT NULL DEFAULT 1,
+    unranked_scoring character varying NOT NULL DEFAULT 'zero',
+    points integer[],
+    method character varying NOT NULL DEFAULT 'irv',
+    tie_break character varying NOT NULL DEFAULT 'random',
+    tie_break_order character varying[],
+    seed bigint NOT NULL DEFAULT 0,
+    moderate_write_ins boolean NOT NULL DEFAULT false,
+    write_ins_per_identity smallint,
+    private boolean NOT NULL DEFAULT false,
+    hide_ballots boolean NOT NULL DEFAULT false,
+    require_oidc boolean NOT NULL DEFAULT false
 );
 CREATE INDEX expires_index ON poll USING btree
     (expires ASC NULLS LAST);
+CREATE INDEX poll_owner_index
+    ON poll(owner_id, expires, id);
 
 --CANDIDATE--
 CREATE TABLE candidate
@@ -21,6 +35,9 @@ CREATE TABLE candidate
 
     name character varying NOT NULL,
     description character varying,
+    withdrawn boolean NOT NULL DEFAULT false,
+    pending boolean NOT NULL DEFAULT false,
+    submitter_id character varying,
 
     poll_id character varying NOT NULL,
 
@@ -34,6 +51,20 @@ CREATE TABLE candidate
 CREATE INDEX fki_candidate_poll_fkey
     ON candidate(poll_id);
 
+--POLL_ADMIN--
+CREATE TABLE poll_admin
+(
+    poll_id character varying NOT NULL,
+    admin_id character varying NOT NULL,
+
+    CONSTRAINT poll_admin_pkey PRIMARY KEY (poll_id, admin_id),
+
+    CONSTRAINT poll_admin_poll_fkey FOREIGN KEY (poll_id)
+        REFERENCES poll (id)
+        ON UPDATE RESTRICT
+        ON DELETE CASCADE
+);
+
 --BALLOT--
 CREATE TABLE ballot
 (
@@ -56,6 +87,32 @@ CREATE TABLE ballot
 CREATE INDEX fki_ballot_poll_fkey
     ON ballot(poll_id);
 
+CREATE INDEX ballot_owner_index
+    ON ballot(owner_id);
+
+CREATE INDEX ballot_poll_timestamp_index
+    ON ballot(poll_id, timestamp, id);
+
+--INVITATION--
+CREATE TABLE invitation
+(
+    token character varying NOT NULL,
+    CONSTRAINT invitation_pkey PRIMARY KEY (token),
+
+    invitee character varying NOT NULL,
+    redeemed_by character varying,
+
+    poll_id character varying NOT NULL,
+
+    CONSTRAINT invitation_poll_fkey FOREIGN KEY (poll_id)
+        REFERENCES poll (id)
+        ON UPDATE RESTRICT
+        ON DELETE CASCADE,
+
+    UNIQUE (poll_id, invitee),
+    UNIQUE (poll_id, redeemed_by)
+);
+
 --BALLOT_RANKING--
 CREATE TABLE ranking
 (
@@ -82,4 +139,13 @@ CREATE INDEX fki_ranking_ballot_poll_fkey
     ON ranking(ballot_id, poll_id);
 
 CREATE INDEX fki_ranking_poll_fkey
-    ON ranking(poll_id);
\ No newline at end of file
+    ON ranking(poll_id);
+
+--MIGRATION--
+CREATE TABLE migration
+(
+    name character varying NOT NULL,
+    applied timestamp with time zone NOT NULL DEFAULT now(),
+
+    CONSTRAINT migration_pkey PRIMARY KEY (name)
+);
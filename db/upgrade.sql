@@ -0,0 +1,73 @@
+-- Brings a database created by an earlier db/create.sql up to date. Safe to run more than once.
+
+--POLL--
+ALTER TABLE poll ALTER COLUMN description DROP NOT NULL;
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS seats smallint NOT NULL DEFAULT 1;
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS unranked_scoring character varying NOT NULL DEFAULT 'zero';
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS points integer[];
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS method character varying NOT NULL DEFAULT 'irv';
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS tie_break character varying NOT NULL DEFAULT 'random';
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS tie_break_order character varying[];
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS seed bigint NOT NULL DEFAULT 0;
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS moderate_write_ins boolean NOT NULL DEFAULT false;
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS write_ins_per_identity smallint;
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS private boolean NOT NULL DEFAULT false;
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS hide_ballots boolean NOT NULL DEFAULT false;
+ALTER TABLE poll ADD COLUMN IF NOT EXISTS require_oidc boolean NOT NULL DEFAULT false;
+CREATE INDEX IF NOT EXISTS poll_owner_index
+    ON poll(owner_id, expires, id);
+
+--CANDIDATE--
+ALTER TABLE candidate ADD COLUMN IF NOT EXISTS withdrawn boolean NOT NULL DEFAULT false;
+ALTER TABLE candidate ADD COLUMN IF NOT EXISTS pending boolean NOT NULL DEFAULT false;
+ALTER TABLE candidate ADD COLUMN IF NOT EXISTS submitter_id character varying;
+
+--POLL_ADMIN--
+CREATE TABLE IF NOT EXISTS poll_admin
+(
+    poll_id character varying NOT NULL,
+    admin_id character varying NOT NULL,
+
+    CONSTRAINT poll_admin_pkey PRIMARY KEY (poll_id, admin_id),
+
+    CONSTRAINT poll_admin_poll_fkey FOREIGN KEY (poll_id)
+        REFERENCES poll (id)
+        ON UPDATE RESTRICT
+        ON DELETE CASCADE
+);
+
+--BALLOT--
+CREATE INDEX IF NOT EXISTS ballot_owner_index
+    ON ballot(owner_id);
+
+CREATE INDEX IF NOT EXISTS ballot_poll_timestamp_index
+    ON ballot(poll_id, timestamp, id);
+
+--INVITATION--
+CREATE TABLE IF NOT EXISTS invitation
+(
+    token character varying NOT NULL,
+    CONSTRAINT invitation_pkey PRIMARY KEY (token),
+
+    invitee character varying NOT NULL,
+    redeemed_by character varying,
+
+    poll_id character varying NOT NULL,
+
+    CONSTRAINT invitation_poll_fkey FOREIGN KEY (poll_id)
+        REFERENCES poll (id)
+        ON UPDATE RESTRICT
+        ON DELETE CASCADE,
+
+    UNIQUE (poll_id, invitee),
+    UNIQUE (poll_id, redeemed_by)
+);
+
+--MIGRATION--
+CREATE TABLE IF NOT EXISTS migration
+(
+    name character varying NOT NULL,
+    applied timestamp with time zone NOT NULL DEFAULT now(),
+
+    CONSTRAINT migration_pkey PRIMARY KEY (name)
+);
@@ -22,6 +22,18 @@ pub struct Poll {
     pub expires: Timestamp,
     pub close: Option<Timestamp>,
     pub write_ins: bool,
+    pub seats: i16,
+    pub unranked_scoring: String,
+    pub points: Option<Vec<i32>>,
+    pub method: String,
+    pub tie_break: String,
+    pub tie_break_order: Option<Vec<String>>,
+    pub seed: i64,
+    pub moderate_write_ins: bool,
+    pub write_ins_per_identity: Option<i16>,
+    pub private: bool,
+    pub hide_ballots: bool,
+    pub require_oidc: bool,
 }
 
 #[derive(sqlx::FromRow, Debug, Eq, PartialEq)]
@@ -30,6 +42,9 @@ pub struct Candidate {
     pub id: i32,
     pub name: String,
     pub description: Option<String>,
+    pub withdrawn: bool,
+    pub pending: bool,
+    pub submitter_id: Option<String>,
 }
 
 #[derive(sqlx::FromRow, Debug, Eq, PartialEq)]
@@ -41,6 +56,42 @@ pub struct Ballot {
     pub owner_id: String,
 }
 
+#[derive(sqlx::FromRow, Debug, Eq, PartialEq)]
+pub struct PollSummary {
+    pub id: String,
+    pub name: String,
+    pub expires: Timestamp,
+    pub close: Option<Timestamp>,
+    pub ballots: i64,
+}
+
+#[derive(sqlx::FromRow, Debug, Eq, PartialEq)]
+pub struct Invitation {
+    pub token: String,
+    pub invitee: String,
+    pub redeemed_by: Option<String>,
+}
+
+/// A ballot along with the poll it was cast in.
+#[derive(sqlx::FromRow, Debug, Eq, PartialEq)]
+pub struct OwnedBallot {
+    pub id: String,
+    pub name: String,
+    pub timestamp: Timestamp,
+    pub poll_id: String,
+    pub poll_name: String,
+    pub expires: Timestamp,
+    pub close: Option<Timestamp>,
+}
+
+#[derive(sqlx::FromRow, Debug, Eq, PartialEq)]
+pub struct NamedRanking {
+    pub ballot_id: String,
+    pub poll_id: String,
+    pub candidate: String,
+    pub ranking: i16,
+}
+
 #[derive(sqlx::FromRow, Debug, Eq, PartialEq)]
 pub struct Ranking {
     pub ballot_id: String,
@@ -67,8 +118,8 @@ pub mod test_db {
     const DATABASE_URL: &str = "PICKYPOLL_TEST_DB";
 
     pub async fn new_pool() -> Pool<Postgres> {
-        let db_url = &env::var(&DATABASE_URL)
-            .expect(&format!("env variable for {} must be set", DATABASE_URL));
+        let db_url = &env::var(DATABASE_URL)
+            .unwrap_or_else(|_| panic!("env variable for {} must be set", DATABASE_URL));
         PgPoolOptions::new()
             .max_connections(1)
             .connect(db_url)
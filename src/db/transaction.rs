@@ -35,13 +35,110 @@ impl<'a> PickyPollTransaction<'a> {
         .await
     }
 
+    /// A page of the poll's ballots ordered by timestamp, then id, starting after `after`
+    /// and limited to timestamps in `[from, to)` when given.
+    pub async fn select_ballots_page(&mut self,
+        poll_id: &str,
+        after: Option<(Timestamp, &str)>,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+        limit: i64,
+    ) -> Result<Vec<Ballot>, sqlx::Error> {
+        let (after_timestamp, after_id) = after.unzip();
+        sqlx::query_as(
+            "select id, name, timestamp, owner_id from ballot \
+            where poll_id = $1 \
+                and ($2::timestamptz is null or (timestamp, id) > ($2, $3)) \
+                and ($4::timestamptz is null or timestamp >= $4) \
+                and ($5::timestamptz is null or timestamp < $5) \
+            order by timestamp, id \
+            limit $6"
+        ).bind(poll_id)
+        .bind(after_timestamp)
+        .bind(after_id)
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_rankings_for_ballots(&mut self, poll_id: &str, ballot_ids: &[String])
+    -> Result<Vec<NamedRanking>, sqlx::Error> {
+        sqlx::query_as(
+            "select ranking.ballot_id, ranking.poll_id, candidate.name as candidate, ranking.ranking \
+            from ranking join candidate on candidate.id = ranking.candidate_id \
+            where ranking.poll_id = $1 and ranking.ballot_id = any($2) \
+            order by ranking.ranking"
+        ).bind(poll_id)
+        .bind(ballot_ids)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_ballots_by_owner(&mut self, owner_id: &str)
+    -> Result<Vec<OwnedBallot>, sqlx::Error> {
+        sqlx::query_as(
+            "select ballot.id, ballot.name, ballot.timestamp, ballot.poll_id, \
+                poll.name as poll_name, poll.expires, poll.close \
+            from ballot join poll on poll.id = ballot.poll_id \
+            where ballot.owner_id = $1 \
+            order by ballot.timestamp desc, ballot.poll_id, ballot.id"
+        ).bind(owner_id)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    /// Rankings on all of `owner_id`'s ballots, in ranked order.
+    pub async fn select_rankings_by_owner(&mut self, owner_id: &str)
+    -> Result<Vec<NamedRanking>, sqlx::Error> {
+        sqlx::query_as(
+            "select ranking.ballot_id, ranking.poll_id, candidate.name as candidate, ranking.ranking \
+            from ranking \
+            join ballot on ballot.id = ranking.ballot_id and ballot.poll_id = ranking.poll_id \
+            join candidate on candidate.id = ranking.candidate_id \
+            where ballot.owner_id = $1 \
+            order by ranking.ranking"
+        ).bind(owner_id)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_ballot_rankings(&mut self, poll_id: &str, ballot_id: &str)
+    -> Result<Vec<NamedRanking>, sqlx::Error> {
+        sqlx::query_as(
+            "select ranking.ballot_id, ranking.poll_id, candidate.name as candidate, ranking.ranking \
+            from ranking join candidate on candidate.id = ranking.candidate_id \
+            where ranking.poll_id = $1 and ranking.ballot_id = $2 \
+            order by ranking.ranking"
+        ).bind(poll_id)
+        .bind(ballot_id)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn count_ballots(&mut self, poll_id: &str) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("select count(*) from ballot where poll_id = $1")
+        .bind(poll_id)
+        .fetch_one(&mut self.tx)
+        .await
+    }
+
+    pub async fn count_ballots_by_owner(&mut self, poll_id: &str, owner_id: &str) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("select count(*) from ballot where poll_id = $1 and owner_id = $2")
+        .bind(poll_id)
+        .bind(owner_id)
+        .fetch_one(&mut self.tx)
+        .await
+    }
+
     pub async fn insert_ballot(&mut self, poll_id: &str, ballot: &Ballot)
     -> Result<PgDone, sqlx::Error> {
         sqlx::query(
             "insert into ballot(id, name, timestamp, owner_id, poll_id) values ($1, $2, $3, $4, $5)"
         ).bind(&ballot.id)
         .bind(&ballot.name)
-        .bind(&ballot.timestamp)
+        .bind(ballot.timestamp)
         .bind(&ballot.owner_id)
         .bind(poll_id)
         .execute(&mut self.tx)
@@ -52,7 +149,7 @@ impl<'a> PickyPollTransaction<'a> {
     -> Result<PgDone, sqlx::Error> {
         sqlx::query(
             "update ballot set timestamp=$1 where id = $2 and name = $3 and poll_id = $4"
-        ).bind(&ballot.timestamp)
+        ).bind(ballot.timestamp)
         .bind(&ballot.id)
         .bind(&ballot.name)
         .bind(poll_id)
@@ -60,39 +157,94 @@ impl<'a> PickyPollTransaction<'a> {
         .await
     }
 
+    pub async fn delete_ballot(&mut self, poll_id: &str, ballot_id: &str)
+    -> Result<PgDone, sqlx::Error> {
+        sqlx::query(
+            "delete from ballot where id = $1 and poll_id = $2"
+        ).bind(ballot_id)
+        .bind(poll_id)
+        .execute(&mut self.tx)
+        .await
+    }
+
     pub async fn select_candidates(&mut self, poll_id: &str)
     -> Result<Vec<Candidate>, sqlx::Error> {
         sqlx::query_as::<_, Candidate>(
-            "select id, name, description from candidate where poll_id = $1"
+            "select id, name, description, withdrawn, pending, submitter_id \
+            from candidate where poll_id = $1 order by id"
         ).bind(poll_id)
         .fetch_all(&mut self.tx)
         .await
     }
 
-    pub async fn insert_candidate(&mut self, poll_id: &str, name: &str, description: &Option<String>)
-    -> Result<PgDone, sqlx::Error>{
-        sqlx::query("insert into candidate(poll_id, name, description) values ($1, $2, $3)")
-        .bind(poll_id)
+    pub async fn insert_candidate(&mut self,
+        poll_id: &str,
+        name: &str,
+        description: &Option<String>,
+        pending: bool,
+        submitter_id: Option<&str>,
+    ) -> Result<PgDone, sqlx::Error>{
+        sqlx::query(
+            "insert into candidate(poll_id, name, description, pending, submitter_id) values ($1, $2, $3, $4, $5)"
+        ).bind(poll_id)
         .bind(name)
         .bind(description)
+        .bind(pending)
+        .bind(submitter_id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn delete_candidate(&mut self, id: i32) -> Result<PgDone, sqlx::Error> {
+        sqlx::query("delete from candidate where id = $1")
+        .bind(id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn update_candidate(&mut self, candidate: &Candidate)
+    -> Result<PgDone, sqlx::Error> {
+        sqlx::query(
+            "update candidate set name = $1, description = $2, withdrawn = $3, pending = $4 where id = $5"
+        ).bind(&candidate.name)
+        .bind(&candidate.description)
+        .bind(candidate.withdrawn)
+        .bind(candidate.pending)
+        .bind(candidate.id)
         .execute(&mut self.tx)
         .await
     }
 
     pub async fn select_poll(&mut self, id: &str) -> Result<Option<Poll>, sqlx::Error> {
         sqlx::query_as::<_, Poll>(
-            "select id, name, description, owner_id, expires, close, write_ins \
+            "select id, name, description, owner_id, expires, close, write_ins, seats, \
+            unranked_scoring, points, method, tie_break, tie_break_order, seed, moderate_write_ins, \
+            write_ins_per_identity, private, hide_ballots, require_oidc \
             from poll where id=$1",
         ).bind(id)
         .fetch_optional(&mut self.tx)
         .await
     }
 
+    /// Like `select_poll`, but holds a row lock on the poll until the transaction ends.
+    pub async fn select_poll_for_update(&mut self, id: &str) -> Result<Option<Poll>, sqlx::Error> {
+        sqlx::query_as::<_, Poll>(
+            "select id, name, description, owner_id, expires, close, write_ins, seats, \
+            unranked_scoring, points, method, tie_break, tie_break_order, seed, moderate_write_ins, \
+            write_ins_per_identity, private, hide_ballots, require_oidc \
+            from poll where id=$1 for update",
+        ).bind(id)
+        .fetch_optional(&mut self.tx)
+        .await
+    }
+
     pub async fn insert_poll(&mut self, poll: &Poll) -> Result<PgDone, sqlx::Error> {
         sqlx::query(
             "insert \
-                into poll(id, name, description, owner_id, expires, close, write_ins) \
-                values ($1, $2, $3, $4, $5, $6, $7)"
+                into poll(id, name, description, owner_id, expires, close, write_ins, seats, \
+                    unranked_scoring, points, method, tie_break, tie_break_order, seed, moderate_write_ins, \
+                    write_ins_per_identity, private, hide_ballots, require_oidc) \
+                values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)"
         ).bind(&poll.id)
         .bind(&poll.name)
         .bind(&poll.description)
@@ -100,6 +252,210 @@ impl<'a> PickyPollTransaction<'a> {
         .bind(poll.expires)
         .bind(poll.close)
         .bind(poll.write_ins)
+        .bind(poll.seats)
+        .bind(&poll.unranked_scoring)
+        .bind(&poll.points)
+        .bind(&poll.method)
+        .bind(&poll.tie_break)
+        .bind(&poll.tie_break_order)
+        .bind(poll.seed)
+        .bind(poll.moderate_write_ins)
+        .bind(poll.write_ins_per_identity)
+        .bind(poll.private)
+        .bind(poll.hide_ballots)
+        .bind(poll.require_oidc)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn update_poll(&mut self, poll: &Poll) -> Result<PgDone, sqlx::Error> {
+        sqlx::query(
+            "update poll set name = $1, description = $2, write_ins = $3, close = $4 where id = $5"
+        ).bind(&poll.name)
+        .bind(&poll.description)
+        .bind(poll.write_ins)
+        .bind(poll.close)
+        .bind(&poll.id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn update_poll_tie_break_order(&mut self, id: &str, tie_break_order: &Option<Vec<String>>)
+    -> Result<PgDone, sqlx::Error> {
+        sqlx::query("update poll set tie_break_order = $1 where id = $2")
+        .bind(tie_break_order)
+        .bind(id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn update_poll_close(&mut self, id: &str, close: Timestamp) -> Result<PgDone, sqlx::Error> {
+        sqlx::query("update poll set close = $1 where id = $2")
+        .bind(close)
+        .bind(id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    /// Polls owned by `owner_id` ordered by expiry, then id, starting after `after`.
+    pub async fn select_polls_by_owner(&mut self, owner_id: &str, after: Option<(Timestamp, &str)>, limit: i64)
+    -> Result<Vec<PollSummary>, sqlx::Error> {
+        let (after_expires, after_id) = after.unzip();
+        sqlx::query_as(
+            "select id, name, expires, close, \
+                (select count(*) from ballot where ballot.poll_id = poll.id) as ballots \
+            from poll \
+            where owner_id = $1 and ($2::timestamptz is null or (expires, id) > ($2, $3)) \
+            order by expires, id \
+            limit $4"
+        ).bind(owner_id)
+        .bind(after_expires)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_expired_poll_ids(&mut self, now: Timestamp, limit: i64)
+    -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "select id from poll where expires <= $1 order by expires limit $2"
+        ).bind(now)
+        .bind(limit)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn delete_polls(&mut self, ids: &[String]) -> Result<PgDone, sqlx::Error> {
+        sqlx::query("delete from poll where id = any($1)")
+        .bind(ids)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    /// Owner ids that start with none of `known_prefixes`.
+    pub async fn select_legacy_owner_ids(&mut self, known_prefixes: &[&str])
+    -> Result<Vec<String>, sqlx::Error> {
+        let patterns: Vec<String> = known_prefixes.iter()
+            .map(|prefix| format!("{}%", prefix))
+            .collect();
+        sqlx::query_scalar(
+            "select owner_id from poll where not owner_id like any($1) \
+            union select owner_id from ballot where not owner_id like any($1) \
+            union select submitter_id from candidate where not submitter_id like any($1)"
+        ).bind(patterns)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    /// Records a one-time migration as applied. Nothing is inserted if it already was; a
+    /// concurrent transaction recording the same migration blocks this one until it ends.
+    pub async fn insert_migration(&mut self, name: &str) -> Result<PgDone, sqlx::Error> {
+        sqlx::query("insert into migration (name) values ($1) on conflict do nothing")
+        .bind(name)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn replace_owner_id(&mut self, old: &str, new: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("update poll set owner_id = $2 where owner_id = $1")
+        .bind(old)
+        .bind(new)
+        .execute(&mut self.tx)
+        .await?;
+        sqlx::query("update ballot set owner_id = $2 where owner_id = $1")
+        .bind(old)
+        .bind(new)
+        .execute(&mut self.tx)
+        .await?;
+        sqlx::query("update candidate set submitter_id = $2 where submitter_id = $1")
+        .bind(old)
+        .bind(new)
+        .execute(&mut self.tx)
+        .await?;
+        Ok(())
+    }
+
+    /// The poll's owner is always an admin, without a `poll_admin` row.
+    pub async fn is_poll_admin(&mut self, poll_id: &str, admin_id: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            "select exists(select 1 from poll where id = $1 and owner_id = $2) \
+            or exists(select 1 from poll_admin where poll_id = $1 and admin_id = $2)"
+        ).bind(poll_id)
+        .bind(admin_id)
+        .fetch_one(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_poll_admins(&mut self, poll_id: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("select admin_id from poll_admin where poll_id = $1 order by admin_id")
+        .bind(poll_id)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn insert_poll_admin(&mut self, poll_id: &str, admin_id: &str) -> Result<PgDone, sqlx::Error> {
+        sqlx::query("insert into poll_admin(poll_id, admin_id) values ($1, $2) on conflict do nothing")
+        .bind(poll_id)
+        .bind(admin_id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn delete_poll_admin(&mut self, poll_id: &str, admin_id: &str) -> Result<PgDone, sqlx::Error> {
+        sqlx::query("delete from poll_admin where poll_id = $1 and admin_id = $2")
+        .bind(poll_id)
+        .bind(admin_id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_invitations(&mut self, poll_id: &str) -> Result<Vec<Invitation>, sqlx::Error> {
+        sqlx::query_as(
+            "select token, invitee, redeemed_by from invitation where poll_id = $1 order by invitee"
+        ).bind(poll_id)
+        .fetch_all(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_invitation(&mut self, poll_id: &str, token: &str)
+    -> Result<Option<Invitation>, sqlx::Error> {
+        sqlx::query_as(
+            "select token, invitee, redeemed_by from invitation where poll_id = $1 and token = $2"
+        ).bind(poll_id)
+        .bind(token)
+        .fetch_optional(&mut self.tx)
+        .await
+    }
+
+    pub async fn select_invitation_by_redeemer(&mut self, poll_id: &str, owner_id: &str)
+    -> Result<Option<Invitation>, sqlx::Error> {
+        sqlx::query_as(
+            "select token, invitee, redeemed_by from invitation where poll_id = $1 and redeemed_by = $2"
+        ).bind(poll_id)
+        .bind(owner_id)
+        .fetch_optional(&mut self.tx)
+        .await
+    }
+
+    pub async fn insert_invitation(&mut self, poll_id: &str, invitation: &Invitation)
+    -> Result<PgDone, sqlx::Error> {
+        sqlx::query(
+            "insert into invitation(token, invitee, redeemed_by, poll_id) values ($1, $2, $3, $4)"
+        ).bind(&invitation.token)
+        .bind(&invitation.invitee)
+        .bind(&invitation.redeemed_by)
+        .bind(poll_id)
+        .execute(&mut self.tx)
+        .await
+    }
+
+    /// Only redeems an invitation that hasn't been redeemed yet.
+    pub async fn redeem_invitation(&mut self, token: &str, owner_id: &str) -> Result<PgDone, sqlx::Error> {
+        sqlx::query(
+            "update invitation set redeemed_by = $2 where token = $1 and redeemed_by is null"
+        ).bind(token)
+        .bind(owner_id)
         .execute(&mut self.tx)
         .await
     }
@@ -112,6 +468,13 @@ impl<'a> PickyPollTransaction<'a> {
         .await
     }
 
+    pub async fn count_rankings_for_candidate(&mut self, candidate_id: i32) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("select count(*) from ranking where candidate_id = $1")
+        .bind(candidate_id)
+        .fetch_one(&mut self.tx)
+        .await
+    }
+
     pub async fn delete_rankings(&mut self, poll_id: &str, ballot_id: &str)
     -> Result<PgDone, sqlx::Error> {
         sqlx::query(
@@ -7,7 +7,11 @@ use actix_web::{App, HttpServer};
 use sqlx::postgres::PgPoolOptions;
 
 use db::PickyDb;
-use operations::PollOperations;
+use operations::{ExpiryLimits, PollOperations};
+use purge::PurgeSettings;
+use secret::SecretHasher;
+use oidc::OidcValidator;
+use token::TokenValidator;
 use std::time::Duration;
 
 mod model;
@@ -15,14 +19,42 @@ mod service;
 mod util;
 mod db;
 mod operations;
+mod purge;
+mod secret;
+mod token;
+mod oidc;
+mod tabulation;
 
 const DB_URL: &str = "PICKYPOLL_DB_URL";
+const DEFAULT_EXPIRY_HOURS: &str = "PICKYPOLL_DEFAULT_EXPIRY_HOURS";
+const MIN_EXPIRY_HOURS: &str = "PICKYPOLL_MIN_EXPIRY_HOURS";
+const MAX_EXPIRY_HOURS: &str = "PICKYPOLL_MAX_EXPIRY_HOURS";
+const PURGE_INTERVAL_SECS: &str = "PICKYPOLL_PURGE_INTERVAL_SECS";
+const PURGE_BATCH_SIZE: &str = "PICKYPOLL_PURGE_BATCH_SIZE";
+const PURGE_DRY_RUN: &str = "PICKYPOLL_PURGE_DRY_RUN";
+const SECRET_HASH_KEY: &str = "PICKYPOLL_SECRET_HASH_KEY";
+const TOKEN_KEY: &str = "PICKYPOLL_TOKEN_KEY";
+const OIDC_ISSUER: &str = "PICKYPOLL_OIDC_ISSUER";
+const OIDC_CLIENT_ID: &str = "PICKYPOLL_OIDC_CLIENT_ID";
+const OIDC_JWKS: &str = "PICKYPOLL_OIDC_JWKS";
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) => value.parse()
+            .unwrap_or_else(|_| panic!("Failed to parse {} from environment", name)),
+        Err(_) => default,
+    }
+}
+
+fn env_hours(name: &str, default: chrono::Duration) -> chrono::Duration {
+    chrono::Duration::hours(env_or(name, default.num_hours()))
+}
 
 #[actix_web::main]
 async fn main() {
     env_logger::init();
-    let db_url = &env::var(&DB_URL)
-        .expect(format!("Failed to get {} from environment", DB_URL).as_str());
+    let db_url = &env::var(DB_URL)
+        .unwrap_or_else(|_| panic!("Failed to get {} from environment", DB_URL));
     let pool = PgPoolOptions::new()
         .min_connections(1)
         .max_connections(4)
@@ -33,12 +65,65 @@ async fn main() {
         .expect("Failed to create database pool");
     pool.acquire().await.expect("Failed to connect to database");
 
+    let secret_hash_key = env::var(SECRET_HASH_KEY)
+        .unwrap_or_else(|_| panic!("Failed to get {} from environment", SECRET_HASH_KEY));
+    let hasher = SecretHasher::new(secret_hash_key.as_bytes());
+    let migrated = secret::migrate_legacy_secrets(&PickyDb::new(pool.clone()), &hasher)
+        .await
+        .expect("Failed to hash legacy voter secrets; has db/upgrade.sql been applied?");
+    if migrated > 0 {
+        info!("Hashed {} legacy voter secrets", migrated);
+    }
+
+    let token_validator = env::var(TOKEN_KEY).ok()
+        .map(|key| TokenValidator::new(key.as_bytes()));
+
+    let oidc_validator = match env::var(OIDC_ISSUER) {
+        Ok(issuer) => {
+            let client_id = env::var(OIDC_CLIENT_ID)
+                .unwrap_or_else(|_| panic!("{} is required with {}", OIDC_CLIENT_ID, OIDC_ISSUER));
+            let jwks = env::var(OIDC_JWKS)
+                .unwrap_or_else(|_| panic!("{} is required with {}", OIDC_JWKS, OIDC_ISSUER));
+            let validator = OidcValidator::load(&issuer, &client_id, &jwks)
+                .await
+                .unwrap_or_else(|e| panic!("Failed to load OIDC keys from {}: {}", jwks, e));
+            Some(validator)
+        },
+        Err(_) => None,
+    };
+
+    let defaults = ExpiryLimits::default();
+    let expiry_limits = ExpiryLimits {
+        default: env_hours(DEFAULT_EXPIRY_HOURS, defaults.default),
+        min: env_hours(MIN_EXPIRY_HOURS, defaults.min),
+        max: env_hours(MAX_EXPIRY_HOURS, defaults.max),
+    };
+    assert!(expiry_limits.min <= expiry_limits.default && expiry_limits.default <= expiry_limits.max,
+        "{} must be between {} and {}", DEFAULT_EXPIRY_HOURS, MIN_EXPIRY_HOURS, MAX_EXPIRY_HOURS);
+
+    let defaults = PurgeSettings::default();
+    let purge_settings = PurgeSettings {
+        interval: Duration::from_secs(env_or(PURGE_INTERVAL_SECS, defaults.interval.as_secs())),
+        batch_size: env_or(PURGE_BATCH_SIZE, defaults.batch_size),
+        dry_run: env_or(PURGE_DRY_RUN, defaults.dry_run),
+    };
+    assert!(purge_settings.interval.as_secs() > 0, "{} must be at least 1", PURGE_INTERVAL_SECS);
+    assert!(purge_settings.batch_size > 0, "{} must be at least 1", PURGE_BATCH_SIZE);
+    actix_web::rt::spawn(purge::run(PickyDb::new(pool.clone()), purge_settings));
+
     let app = move || {
         let db = PickyDb::new(pool.clone());
-        let ops = PollOperations::new(db);
-        App::new()
+        let ops = PollOperations::new(db, expiry_limits.clone());
+        let mut app = App::new()
             .data(ops)
-            .configure(service::config::<PollOperations>)
+            .data(hasher.clone());
+        if let Some(validator) = &token_validator {
+            app = app.data(validator.clone());
+        }
+        if let Some(validator) = &oidc_validator {
+            app = app.data(validator.clone());
+        }
+        app.configure(service::config::<PollOperations>)
     };
     HttpServer::new(app).bind(("0.0.0.0", 8080))
         .expect("HTTP server failed to bind to 8080")
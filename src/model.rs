@@ -1,5 +1,5 @@
 use chrono::{DateTime, offset::Utc};
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
@@ -7,6 +7,10 @@ use serde::{Deserialize, Serialize};
 pub struct PostPollRequest {
     pub name: String,
     pub description: Option<String>,
+    #[serde(default)]
+    pub expires: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub close: Option<DateTime<Utc>>,
     pub configuration: Configuration,
     pub candidates: Vec<Candidate>,
 }
@@ -14,6 +18,7 @@ pub struct PostPollRequest {
 #[derive(Serialize, Deserialize)]
 pub struct GetPollResponse {
     pub poll: Poll,
+    pub closed: bool,
     pub ballots: Vec<BallotSummary>,
 }
 
@@ -27,6 +32,7 @@ pub struct Poll {
     pub close: Option<DateTime<Utc>>,
     pub candidates: Vec<Candidate>,
     pub configuration: Configuration,
+    pub seed: i64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -41,12 +47,205 @@ pub struct BallotSummary {
 pub struct Candidate {
     pub name: String,
     pub description: Option<String>,
+    #[serde(default, skip_deserializing)]
+    pub withdrawn: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetCandidatesResponse {
+    pub candidates: Vec<Candidate>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWriteInsResponse {
+    pub write_ins: Vec<WriteIn>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteIn {
+    pub name: String,
+    pub description: Option<String>,
+    pub pending: bool,
+    pub withdrawn: bool,
+    /// The submitter's owner id: a hashed secret, or a prefixed token or OIDC subject,
+    /// as returned by `GET /me`.
+    pub submitter_id: String,
+    /// The name on the submitter's ballot in this poll, if they have voted.
+    pub submitter: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetMeResponse {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostAdminRequest {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetAdminsResponse {
+    pub admins: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostInvitationsRequest {
+    pub invitees: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetInvitationsResponse {
+    pub invitations: Vec<Invitation>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Invitation {
+    pub invitee: String,
+    pub token: String,
+    pub redeemed: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PatchCandidateRequest {
+    pub name: Option<String>,
+    /// Left out to keep the description, null to clear it.
+    #[serde(default, deserialize_with = "crate::util::nullable", skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+    pub withdrawn: Option<bool>,
 }
 
 #[derive(Serialize, Clone, Debug, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Configuration {
-    pub write_ins: bool
+    pub write_ins: bool,
+    #[serde(default = "default_seats")]
+    pub seats: u16,
+    #[serde(default = "default_unranked_scoring")]
+    pub unranked_scoring: UnrankedScoring,
+    #[serde(default)]
+    pub points: Option<Vec<u16>>,
+    #[serde(default = "default_method")]
+    pub method: TabulationMethod,
+    #[serde(default = "default_tie_break")]
+    pub tie_break: TieBreak,
+    #[serde(default)]
+    pub tie_break_order: Option<Vec<String>>,
+    #[serde(default)]
+    pub moderate_write_ins: bool,
+    #[serde(default)]
+    pub write_ins_per_identity: Option<u16>,
+    /// Only identities that redeemed an invitation may vote, once each.
+    #[serde(default)]
+    pub private: bool,
+    /// Ballots are left out of `GET /polls/{poll_id}` and only their owners may read them.
+    #[serde(default)]
+    pub hide_ballots: bool,
+    /// Only identities signed in through the OIDC issuer may vote or submit write-ins.
+    #[serde(default)]
+    pub require_oidc: bool,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Configuration {
+            write_ins: false,
+            seats: default_seats(),
+            unranked_scoring: default_unranked_scoring(),
+            points: None,
+            method: default_method(),
+            tie_break: default_tie_break(),
+            tie_break_order: None,
+            moderate_write_ins: false,
+            write_ins_per_identity: None,
+            private: false,
+            hide_ballots: false,
+            require_oidc: false,
+        }
+    }
+}
+
+fn default_seats() -> u16 {
+    1
+}
+
+fn default_unranked_scoring() -> UnrankedScoring {
+    UnrankedScoring::Zero
+}
+
+fn default_method() -> TabulationMethod {
+    TabulationMethod::Irv
+}
+
+fn default_tie_break() -> TieBreak {
+    TieBreak::Random
+}
+
+#[derive(Serialize, Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TieBreak {
+    PreviousRounds,
+    Random,
+    Lexicographic,
+    Owner,
+}
+
+impl TieBreak {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TieBreak::PreviousRounds => "previousRounds",
+            TieBreak::Random => "random",
+            TieBreak::Lexicographic => "lexicographic",
+            TieBreak::Owner => "owner",
+        }
+    }
+}
+
+impl FromStr for TieBreak {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "previousRounds" => Ok(TieBreak::PreviousRounds),
+            "random" => Ok(TieBreak::Random),
+            "lexicographic" => Ok(TieBreak::Lexicographic),
+            "owner" => Ok(TieBreak::Owner),
+            _ => Err(format!("Unknown tie break: {}", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UnrankedScoring {
+    Zero,
+    Average,
+    Excluded,
+}
+
+impl UnrankedScoring {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnrankedScoring::Zero => "zero",
+            UnrankedScoring::Average => "average",
+            UnrankedScoring::Excluded => "excluded",
+        }
+    }
+}
+
+impl FromStr for UnrankedScoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "zero" => Ok(UnrankedScoring::Zero),
+            "average" => Ok(UnrankedScoring::Average),
+            "excluded" => Ok(UnrankedScoring::Excluded),
+            _ => Err(format!("Unknown unranked scoring: {}", s)),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -55,6 +254,19 @@ pub struct PostPollResponse {
     pub poll: Poll,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchPollRequest {
+    pub name: Option<String>,
+    /// Left out to keep the description, null to clear it.
+    #[serde(default, deserialize_with = "crate::util::nullable", skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+    pub write_ins: Option<bool>,
+    /// Left out to keep the close time, null to clear it.
+    #[serde(default, deserialize_with = "crate::util::nullable", skip_serializing_if = "Option::is_none")]
+    pub close: Option<Option<DateTime<Utc>>>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(test, derive(Clone))]
 pub struct PutBallotRequest {
@@ -65,5 +277,240 @@ pub struct PutBallotRequest {
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(test, derive(Clone))]
 pub enum Identity {
+    /// A hashed X-VOTE-SECRET header.
     SecretKey(String),
+    /// The user account from a validated bearer token.
+    Token(String),
+    /// The subject of an ID token from the configured OIDC issuer.
+    Oidc(String),
+}
+
+impl Identity {
+    /// The id recorded as the owner of polls, ballots and write-ins.
+    pub fn owner_id(&self) -> &str {
+        match self {
+            Identity::SecretKey(owner_id)
+            | Identity::Token(owner_id)
+            | Identity::Oidc(owner_id) => owner_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TabulationMethod {
+    Irv,
+    Schulze,
+    RankedPairs,
+    Stv,
+    Borda,
+    Dowdall,
+    Positional,
+}
+
+impl TabulationMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TabulationMethod::Irv => "irv",
+            TabulationMethod::Schulze => "schulze",
+            TabulationMethod::RankedPairs => "rankedPairs",
+            TabulationMethod::Stv => "stv",
+            TabulationMethod::Borda => "borda",
+            TabulationMethod::Dowdall => "dowdall",
+            TabulationMethod::Positional => "positional",
+        }
+    }
+}
+
+impl FromStr for TabulationMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "irv" => Ok(TabulationMethod::Irv),
+            "schulze" => Ok(TabulationMethod::Schulze),
+            "rankedPairs" => Ok(TabulationMethod::RankedPairs),
+            "stv" => Ok(TabulationMethod::Stv),
+            "borda" => Ok(TabulationMethod::Borda),
+            "dowdall" => Ok(TabulationMethod::Dowdall),
+            "positional" => Ok(TabulationMethod::Positional),
+            _ => Err(format!("Unknown tabulation method: {}", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetMyBallotsResponse {
+    pub ballots: Vec<MyBallot>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyBallot {
+    pub poll_id: String,
+    pub poll_name: String,
+    pub closed: bool,
+    pub id: String,
+    pub name: String,
+    pub timestamp: DateTime<Utc>,
+    pub rankings: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPollQuery {
+    #[serde(default)]
+    pub omit_ballots: bool,
+}
+
+#[derive(Deserialize)]
+pub struct ListBallotsQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<u16>,
+    /// Only ballots cast at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only ballots cast before this time.
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBallotsResponse {
+    pub ballots: Vec<BallotSummary>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ListPollsQuery {
+    pub owner: String,
+    pub cursor: Option<String>,
+    pub limit: Option<u16>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPollsResponse {
+    pub polls: Vec<PollSummary>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PollSummary {
+    pub id: String,
+    pub name: String,
+    pub ballots: i64,
+    pub closed: bool,
+    pub close: Option<DateTime<Utc>>,
+    pub expires: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+pub struct GetResultsQuery {
+    pub method: Option<TabulationMethod>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetResultsResponse {
+    pub official: bool,
+    pub results: Results,
+    pub ties: Vec<Tie>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Tie {
+    pub round: Option<usize>,
+    pub candidates: Vec<Arc<String>>,
+    pub resolution: TieBreak,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+pub enum Results {
+    Irv(IrvResults),
+    Schulze(SchulzeResults),
+    RankedPairs(RankedPairsResults),
+    Stv(StvResults),
+    Borda(ScoringResults),
+    Dowdall(ScoringResults),
+    Positional(ScoringResults),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IrvResults {
+    pub rounds: Vec<IrvRound>,
+    pub winner: Option<Arc<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IrvRound {
+    pub tallies: Vec<Tally>,
+    pub exhausted: u32,
+    pub eliminated: Option<Arc<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Tally {
+    pub candidate: Arc<String>,
+    pub votes: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchulzeResults {
+    pub candidates: Vec<Arc<String>>,
+    pub pairwise: Vec<Vec<u32>>,
+    pub strongest_paths: Vec<Vec<u32>>,
+    pub order: Vec<Vec<Arc<String>>>,
+    pub winner: Option<Arc<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RankedPairsResults {
+    pub pairs: Vec<RankedPair>,
+    pub order: Vec<Vec<Arc<String>>>,
+    pub winner: Option<Arc<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RankedPair {
+    pub winner: Arc<String>,
+    pub loser: Arc<String>,
+    pub votes: u32,
+    pub opposition: u32,
+    pub locked: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StvResults {
+    pub seats: usize,
+    pub quota: f64,
+    pub rounds: Vec<StvRound>,
+    pub elected: Vec<Arc<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StvRound {
+    pub tallies: Vec<WeightedTally>,
+    pub exhausted: f64,
+    pub elected: Vec<Arc<String>>,
+    pub eliminated: Option<Arc<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WeightedTally {
+    pub candidate: Arc<String>,
+    pub votes: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScoringResults {
+    pub scores: Vec<Score>,
+    pub winners: Vec<Arc<String>>,
+    pub winner: Option<Arc<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Score {
+    pub candidate: Arc<String>,
+    pub points: f64,
 }
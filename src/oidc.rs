@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use actix_web::client::Client;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+
+/// Marks an owner id as belonging to a user authenticated by the OIDC issuer.
+pub const OIDC_OWNER_PREFIX: &str = "oidc:";
+
+#[derive(Debug)]
+pub enum LoadJwksError {
+    Read(std::io::Error),
+    Fetch(String),
+    Parse(serde_json::Error),
+}
+
+#[derive(Debug)]
+pub enum ValidateTokenError {
+    UnknownKey,
+    Invalid(jsonwebtoken::errors::Error),
+}
+
+impl fmt::Display for LoadJwksError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadJwksError::Read(e) => write!(f, "couldn't read JWKS file: {}", e),
+            LoadJwksError::Fetch(e) => write!(f, "couldn't fetch JWKS: {}", e),
+            LoadJwksError::Parse(e) => write!(f, "couldn't parse JWKS: {}", e),
+        }
+    }
+}
+
+impl fmt::Display for ValidateTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidateTokenError::UnknownKey => write!(f, "token is not signed with a known key"),
+            ValidateTokenError::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+/// Validates RS256 ID tokens from one OIDC issuer against its published keys. The keys
+/// are loaded once at startup, so a restart is needed after the issuer rotates them.
+#[derive(Clone)]
+pub struct OidcValidator {
+    issuer: String,
+    client_id: String,
+    keys: HashMap<String, (String, String)>,
+}
+
+impl OidcValidator {
+    /// Keeps the RSA keys from a JWKS document; keys without a `kid` are ignored, as
+    /// there'd be no way to pick them for a token.
+    pub fn new(issuer: &str, client_id: &str, jwks: &str) -> Result<OidcValidator, serde_json::Error> {
+        let jwks: Jwks = serde_json::from_str(jwks)?;
+        let keys = jwks.keys.into_iter()
+            .filter(|key| key.kty == "RSA")
+            .filter_map(|key| Some((key.kid?, (key.n?, key.e?))))
+            .collect();
+        Ok(OidcValidator {
+            issuer: issuer.to_string(),
+            client_id: client_id.to_string(),
+            keys,
+        })
+    }
+
+    /// Loads the JWKS from an http(s) URL, or otherwise from a file path.
+    pub async fn load(issuer: &str, client_id: &str, jwks_source: &str) -> Result<OidcValidator, LoadJwksError> {
+        let jwks = if jwks_source.starts_with("http://") || jwks_source.starts_with("https://") {
+            let mut response = Client::default()
+                .get(jwks_source)
+                .send()
+                .await
+                .map_err(|e| LoadJwksError::Fetch(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(LoadJwksError::Fetch(format!("{} returned {}", jwks_source, response.status())));
+            }
+            let body = response.body()
+                .await
+                .map_err(|e| LoadJwksError::Fetch(e.to_string()))?;
+            String::from_utf8_lossy(&body).into_owned()
+        } else {
+            std::fs::read_to_string(jwks_source).map_err(LoadJwksError::Read)?
+        };
+        OidcValidator::new(issuer, client_id, &jwks).map_err(LoadJwksError::Parse)
+    }
+
+    /// Returns the owner id for the ID token's subject.
+    pub fn validate(&self, token: &str) -> Result<String, ValidateTokenError> {
+        let header = jsonwebtoken::decode_header(token).map_err(ValidateTokenError::Invalid)?;
+        let (n, e) = header.kid
+            .and_then(|kid| self.keys.get(&kid))
+            .ok_or(ValidateTokenError::UnknownKey)?;
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.iss = Some(self.issuer.clone());
+        validation.set_audience(&[&self.client_id]);
+
+        let data = jsonwebtoken::decode::<Claims>(token, &DecodingKey::from_rsa_components(n, e), &validation)
+            .map_err(ValidateTokenError::Invalid)?;
+        Ok(format!("{}{}", OIDC_OWNER_PREFIX, data.claims.sub))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, HttpResponse, web};
+    use chrono::{Duration, Utc};
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde::Serialize;
+
+    use super::*;
+
+    const ISSUER: &str = "https://sso.example.com";
+    const CLIENT_ID: &str = "picky-poll";
+    const JWKS: &str = include_str!("../test-data/oidc-jwks.json");
+    const PRIVATE_KEY: &[u8] = include_bytes!("../test-data/oidc-private.pem");
+
+    #[derive(Serialize)]
+    struct TestClaims<'a> {
+        iss: &'a str,
+        aud: &'a str,
+        sub: &'a str,
+        exp: i64,
+    }
+
+    /// Signs an ID token as the stand-in issuer whose keys are in `JWKS`.
+    fn sign(iss: &str, aud: &str, sub: &str) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("test-key".to_string());
+        let claims = TestClaims { iss, aud, sub, exp: (Utc::now() + Duration::minutes(5)).timestamp() };
+        let key = EncodingKey::from_rsa_pem(PRIVATE_KEY).expect("Failed to read test key");
+        jsonwebtoken::encode(&header, &claims, &key).expect("Failed to sign test token")
+    }
+
+    #[test]
+    fn accepts_issuer_token() {
+        let validator = OidcValidator::new(ISSUER, CLIENT_ID, JWKS).unwrap();
+        let token = sign(ISSUER, CLIENT_ID, "employee 1");
+        assert_eq!("oidc:employee 1", validator.validate(&token).unwrap());
+    }
+
+    #[test]
+    fn rejects_other_issuer_or_audience() {
+        let validator = OidcValidator::new(ISSUER, CLIENT_ID, JWKS).unwrap();
+        let token = sign("https://elsewhere.example.com", CLIENT_ID, "employee 1");
+        assert!(matches!(validator.validate(&token), Err(ValidateTokenError::Invalid(_))));
+        let token = sign(ISSUER, "another app", "employee 1");
+        assert!(matches!(validator.validate(&token), Err(ValidateTokenError::Invalid(_))));
+    }
+
+    #[test]
+    fn rejects_unknown_key() {
+        let validator = OidcValidator::new(ISSUER, CLIENT_ID, r#"{"keys": []}"#).unwrap();
+        let token = sign(ISSUER, CLIENT_ID, "employee 1");
+        assert!(matches!(validator.validate(&token), Err(ValidateTokenError::UnknownKey)));
+    }
+
+    #[actix_rt::test]
+    async fn loads_jwks_from_url() {
+        let issuer = actix_web::test::start(|| App::new()
+            .route("/jwks", web::get().to(|| HttpResponse::Ok().body(JWKS))));
+
+        let validator = OidcValidator::load(ISSUER, CLIENT_ID, &issuer.url("/jwks")).await.unwrap();
+        let token = sign(ISSUER, CLIENT_ID, "employee 1");
+        assert_eq!("oidc:employee 1", validator.validate(&token).unwrap());
+    }
+}
@@ -3,13 +3,15 @@ use std::{collections::HashMap, sync::Arc};
 use async_trait::async_trait;
 
 use crate::{model::*, util};
+use crate::tabulation::{self, Positional, TieBreaker};
 use crate::db::{
     self,
     PickyDb,
     PickyPollTransaction,
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use itertools::Itertools;
+use sqlx::Done;
 use rand::{
     distributions::Alphanumeric,
     Rng,
@@ -22,6 +24,8 @@ use mockall::automock;
 #[derive(Debug)]
 pub enum PostPollError {
     DuplicateCandidate(String),
+    InvalidConfiguration(String),
+    InvalidSchedule(String),
     Unexpected,
 }
 
@@ -35,8 +39,12 @@ impl From<sqlx::Error> for PostPollError {
 #[derive(Debug)]
 pub enum PostCandidateError {
     PollNotFound,
+    PollClosed,
     NoWriteIns,
+    OidcRequired,
+    WriteInLimitReached,
     DuplicateCandidate(String),
+    NameUnavailable,
     Unexpected,
 }
 
@@ -47,6 +55,127 @@ impl From<sqlx::Error> for PostCandidateError {
     }
 }
 
+#[derive(Debug)]
+pub enum UpdateCandidateError {
+    PollNotFound,
+    CandidateNotFound,
+    NotOwner,
+    PollClosed,
+    DuplicateCandidate(String),
+    LockedByBallots,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for UpdateCandidateError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum ModerateCandidateError {
+    PollNotFound,
+    PollClosed,
+    CandidateNotFound,
+    NotOwner,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for ModerateCandidateError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum InvitationError {
+    PollNotFound,
+    NotOwner,
+    NotPrivate,
+    DuplicateInvitee(String),
+    Unexpected,
+}
+
+impl From<sqlx::Error> for InvitationError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum RedeemInvitationError {
+    PollNotFound,
+    InvitationNotFound,
+    AlreadyRedeemed,
+    AlreadyInvited,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for RedeemInvitationError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum PollAdminError {
+    PollNotFound,
+    NotOwner,
+    AdminNotFound,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for PollAdminError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum ListPollsError {
+    InvalidCursor,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for ListPollsError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum GetMyBallotsError {
+    Unexpected,
+}
+
+impl From<sqlx::Error> for GetMyBallotsError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum ListBallotsError {
+    PollNotFound,
+    BallotsHidden,
+    InvalidCursor,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for ListBallotsError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
 #[derive(Debug)]
 pub enum GetPollError {
     NotFound,
@@ -60,13 +189,34 @@ impl From<sqlx::Error> for GetPollError {
     }
 }
 
+#[derive(Debug)]
+pub enum GetResultsError {
+    NotFound,
+    InvalidMethod(String),
+    Unexpected,
+}
+
+impl From<GetPollError> for GetResultsError {
+    fn from(e: GetPollError) -> Self {
+        match e {
+            GetPollError::NotFound => Self::NotFound,
+            GetPollError::Unexpected => Self::Unexpected,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum PutBallotError {
     CandidateNotFound(String),
+    CandidateWithdrawn(String),
     DuplicateRanking(String),
     PollNotFound,
+    PollClosed,
     NotOwner,
     NotSameName,
+    NotInvited,
+    OidcRequired,
+    AlreadyVoted,
     Unexpected,
 }
 
@@ -77,6 +227,83 @@ impl From<sqlx::Error> for PutBallotError {
     }
 }
 
+#[derive(Debug)]
+pub enum GetBallotError {
+    PollNotFound,
+    BallotNotFound,
+    NotOwner,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for GetBallotError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum DeleteBallotError {
+    PollNotFound,
+    BallotNotFound,
+    PollClosed,
+    NotOwner,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for DeleteBallotError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum ClosePollError {
+    PollNotFound,
+    NotOwner,
+    AlreadyClosed,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for ClosePollError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum UpdatePollError {
+    PollNotFound,
+    NotOwner,
+    PollClosed,
+    LockedByBallots(&'static str),
+    InvalidSchedule(String),
+    Unexpected,
+}
+
+impl From<sqlx::Error> for UpdatePollError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
+#[derive(Debug)]
+pub enum DeletePollError {
+    PollNotFound,
+    NotOwner,
+    Unexpected,
+}
+
+impl From<sqlx::Error> for DeletePollError {
+    fn from(e: sqlx::Error) -> Self {
+        log_sql_error(e);
+        Self::Unexpected
+    }
+}
+
 fn log_sql_error(e: sqlx::Error) {
     error!("unexpected sql error: {:?}", e);
     if let Some(e) = e.into_database_error() {
@@ -84,18 +311,73 @@ fn log_sql_error(e: sqlx::Error) {
     };
 }
 
+/// A poll stops accepting ballots and candidates once its close time or expiry has passed.
+fn is_closed(poll: &db::Poll) -> bool {
+    has_closed(poll.expires, poll.close)
+}
+
+fn has_closed(expires: DateTime<Utc>, close: Option<DateTime<Utc>>) -> bool {
+    let now = Utc::now();
+    expires <= now || matches!(close, Some(close) if close <= now)
+}
+
+/// Page size for listings when the request doesn't give one, and the most it may ask for.
+const DEFAULT_PAGE_SIZE: u16 = 20;
+const MAX_PAGE_SIZE: u16 = 100;
+
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait PollOperationsT {
     async fn post_poll(&self, identity: &Identity, request: &PostPollRequest) -> Result<PostPollResponse, PostPollError>;
-    async fn post_candidate(&self, poll_id: &str, request: &Candidate) -> Result<(), PostCandidateError>;
-    async fn get_poll(&self, id: &str) -> Result<GetPollResponse, GetPollError>;
+    async fn post_candidate(&self, poll_id: &str, identity: &Identity, request: &Candidate) -> Result<(), PostCandidateError>;
+    async fn update_candidate(&self,
+        poll_id: &str,
+        identity: &Identity,
+        candidate_name: &str,
+        request: &PatchCandidateRequest
+    ) -> Result<(), UpdateCandidateError>;
+    async fn get_pending_candidates(&self, poll_id: &str, identity: &Identity) -> Result<GetCandidatesResponse, ModerateCandidateError>;
+    async fn get_write_ins(&self, poll_id: &str, identity: &Identity) -> Result<GetWriteInsResponse, ModerateCandidateError>;
+    async fn post_invitations(&self,
+        poll_id: &str,
+        identity: &Identity,
+        request: &PostInvitationsRequest,
+    ) -> Result<GetInvitationsResponse, InvitationError>;
+    async fn get_invitations(&self, poll_id: &str, identity: &Identity) -> Result<GetInvitationsResponse, InvitationError>;
+    async fn redeem_invitation(&self, poll_id: &str, identity: &Identity, token: &str) -> Result<(), RedeemInvitationError>;
+    async fn moderate_candidate(&self,
+        poll_id: &str,
+        identity: &Identity,
+        candidate_name: &str,
+        approve: bool
+    ) -> Result<(), ModerateCandidateError>;
+    async fn get_admins(&self, poll_id: &str, identity: &Identity) -> Result<GetAdminsResponse, PollAdminError>;
+    async fn add_admin(&self, poll_id: &str, identity: &Identity, admin_id: &str) -> Result<(), PollAdminError>;
+    async fn remove_admin(&self, poll_id: &str, identity: &Identity, admin_id: &str) -> Result<(), PollAdminError>;
+    async fn list_polls(&self,
+        identity: &Identity,
+        cursor: Option<String>,
+        limit: Option<u16>,
+    ) -> Result<ListPollsResponse, ListPollsError>;
+    async fn get_my_ballots(&self, identity: &Identity) -> Result<GetMyBallotsResponse, GetMyBallotsError>;
+    async fn list_ballots(&self, poll_id: &str, query: &ListBallotsQuery) -> Result<ListBallotsResponse, ListBallotsError>;
+    async fn get_poll(&self, id: &str, include_ballots: bool) -> Result<GetPollResponse, GetPollError>;
+    async fn get_results(&self, poll_id: &str, method: Option<TabulationMethod>) -> Result<GetResultsResponse, GetResultsError>;
+    async fn close_poll(&self, poll_id: &str, identity: &Identity) -> Result<(), ClosePollError>;
+    async fn update_poll(&self, poll_id: &str, identity: &Identity, request: &PatchPollRequest) -> Result<(), UpdatePollError>;
+    async fn delete_poll(&self, poll_id: &str, identity: &Identity) -> Result<(), DeletePollError>;
     async fn put_ballot(&self,
         poll_id: &str,
         user_id: &Identity,
         ballot_id: &str,
         request: &PutBallotRequest
     ) -> Result<(), PutBallotError>;
+    async fn get_ballot(&self,
+        poll_id: &str,
+        user_id: &Option<Identity>,
+        ballot_id: &str,
+    ) -> Result<BallotSummary, GetBallotError>;
+    async fn delete_ballot(&self, poll_id: &str, user_id: &Identity, ballot_id: &str) -> Result<(), DeleteBallotError>;
     async fn insert_rankings<'a>(&self,
         tx: &mut PickyPollTransaction<'a>,
         poll_id: &str,
@@ -104,96 +386,43 @@ pub trait PollOperationsT {
     ) -> Result<(), PutBallotError>;
 }
 
-#[derive(Clone)]
-pub struct PollOperations {
-    db: PickyDb,
+/// Bounds on how long after creation a poll may expire, and the expiry used when a
+/// request doesn't ask for one.
+#[derive(Clone, Debug)]
+pub struct ExpiryLimits {
+    pub default: Duration,
+    pub min: Duration,
+    pub max: Duration,
 }
 
-impl PollOperations {
-    pub fn new(db: PickyDb) -> PollOperations {
-        PollOperations {
-            db
+impl Default for ExpiryLimits {
+    fn default() -> Self {
+        ExpiryLimits {
+            default: Duration::days(7),
+            min: Duration::hours(1),
+            max: Duration::days(90),
         }
     }
 }
 
-#[async_trait]
-impl PollOperationsT for PollOperations {
-
-    async fn post_poll(&self, identity: &Identity, request: &PostPollRequest)
-    -> Result<PostPollResponse, PostPollError> {
-        if let Some(duplicate) = util::first_duplicate(request.candidates.iter().map(|c| &c.name)) {
-            return Err(PostPollError::DuplicateCandidate(duplicate.clone()));
-        }
-
-        let poll_id: String = thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(10)
-        .collect();
-
-        let Identity::SecretKey(owner_id) = identity;
-
-        let mut transaction = self.db.new_transaction().await?;
-
-        let poll = db::Poll {
-            id: poll_id.to_owned(),
-            name: request.name.clone(),
-            description: request.description.clone(),
-            owner_id: owner_id.clone(),
-            expires: Utc::now() + Duration::days(7),
-            close: None,
-            write_ins: request.configuration.write_ins,
-        };
-
-        transaction.insert_poll(&poll).await?;
-
-        for c in request.candidates.iter() {
-            transaction.insert_candidate(&poll_id, &c.name, &c.description).await?;
-        }
-
-        transaction.commit().await?;
-
-        Ok(PostPollResponse {
-            poll: Poll {
-                id: poll_id,
-                name: request.name.clone(),
-                description: request.description.clone(),
-                expires: poll.expires,
-                close: None,
-                configuration: request.configuration.clone(),
-                candidates: request.candidates.clone(),
-            }
-        })
-    }
-
-    async fn post_candidate(&self, poll_id: &str, request: &Candidate) -> Result<(), PostCandidateError> {
-        let mut transaction = self.db.new_transaction()
-        .await?;
-
-        let poll = transaction.select_poll(poll_id)
-        .await?
-        .ok_or(PostCandidateError::PollNotFound)?;
-
-        if !poll.write_ins {
-            return Err(PostCandidateError::NoWriteIns);
-        }
-
-        let mut existing_candidates = transaction.select_candidates(&poll.id)
-        .await?
-        .into_iter()
-        .map(|c| c.name);
+#[derive(Clone)]
+pub struct PollOperations {
+    db: PickyDb,
+    expiry_limits: ExpiryLimits,
+}
 
-        if let Some(_) = existing_candidates.find(|e| e == &request.name) {
-            return Err(PostCandidateError::DuplicateCandidate(request.name.clone()));
+impl PollOperations {
+    pub fn new(db: PickyDb, expiry_limits: ExpiryLimits) -> PollOperations {
+        PollOperations {
+            db,
+            expiry_limits,
         }
-
-        transaction.insert_candidate(&poll_id, &request.name, &request.description).await?;
-        transaction.commit().await?;
-        
-        Ok(())
     }
 
-    async fn get_poll(&self, id: &str) -> Result<GetPollResponse, GetPollError> {
+    /// Loads a poll, with its ballots only if `include_ballots` holds for the stored poll.
+    /// Tabulation needs every ballot, so this bypasses the poll's hideBallots option.
+    async fn load_poll(&self, id: &str, include_ballots: impl FnOnce(&db::Poll) -> bool)
+    -> Result<GetPollResponse, GetPollError> {
         let mut transaction = self.db.new_transaction()
         .await?;
 
@@ -201,12 +430,24 @@ impl PollOperationsT for PollOperations {
             .await?
             .ok_or(GetPollError::NotFound)?;
 
+        let invalid_configuration = |e: String| {
+            error!("Invalid configuration for poll_id={}: {}", &poll.id, e);
+            GetPollError::Unexpected
+        };
+        let unranked_scoring = poll.unranked_scoring.parse()
+            .map_err(invalid_configuration)?;
+        let method = poll.method.parse()
+            .map_err(invalid_configuration)?;
+        let tie_break = poll.tie_break.parse()
+            .map_err(invalid_configuration)?;
+
         let candidates = transaction.select_candidates(id)
         .await?;
-        let ballots = transaction.select_ballots(id)
-        .await?;
-        let rankings = transaction.select_rankings(id)
-        .await?;
+        let (ballots, rankings) = if include_ballots(&poll) {
+            (transaction.select_ballots(id).await?, transaction.select_rankings(id).await?)
+        } else {
+            (Vec::new(), Vec::new())
+        };
 
         let candidate_id_to_name: HashMap<i32, Arc<String>> = candidates.iter()
         .map(|c| (c.id, Arc::new(c.name.clone())))
@@ -227,7 +468,7 @@ impl PollOperationsT for PollOperations {
             .flat_map(|r| {
                 candidate_id_to_name
                 .get(&r.candidate_id)
-                .map(|arc| arc.clone())
+                .cloned()
                 .or_else(|| {
                     error!("Candidate not found for ballot_id={},candidate_id={}", &r.ballot_id, r.candidate_id);
                     None
@@ -243,13 +484,18 @@ impl PollOperationsT for PollOperations {
         }).collect();
         
         let candidates = candidates.into_iter()
+        .filter(|c| !c.pending)
         .map(|c| Candidate {
             name: c.name,
             description: c.description,
+            withdrawn: c.withdrawn,
         })
         .collect();
 
+        let closed = is_closed(&poll);
+
         Ok(GetPollResponse {
+            closed,
             poll: Poll {
                 id: poll.id,
                 name: poll.name,
@@ -259,11 +505,786 @@ impl PollOperationsT for PollOperations {
                 close: poll.close,
                 configuration: Configuration {
                     write_ins: poll.write_ins,
-                }
+                    seats: poll.seats as u16,
+                    unranked_scoring,
+                    points: poll.points
+                        .map(|points| points.into_iter().map(|p| p as u16).collect()),
+                    method,
+                    tie_break,
+                    tie_break_order: poll.tie_break_order,
+                    moderate_write_ins: poll.moderate_write_ins,
+                    write_ins_per_identity: poll.write_ins_per_identity.map(|limit| limit as u16),
+                    private: poll.private,
+                    hide_ballots: poll.hide_ballots,
+                    require_oidc: poll.require_oidc,
+                },
+                seed: poll.seed,
             },
             ballots,
         })
     }
+}
+
+#[async_trait]
+impl PollOperationsT for PollOperations {
+
+    async fn post_poll(&self, identity: &Identity, request: &PostPollRequest)
+    -> Result<PostPollResponse, PostPollError> {
+        if let Some(duplicate) = util::first_duplicate(request.candidates.iter().map(|c| &c.name)) {
+            return Err(PostPollError::DuplicateCandidate(duplicate.clone()));
+        }
+
+        if request.configuration.seats == 0 || request.configuration.seats > i16::MAX as u16 {
+            return Err(PostPollError::InvalidConfiguration(format!("seats must be between 1 and {}", i16::MAX)));
+        }
+
+        if let Some(points) = &request.configuration.points {
+            if points.is_empty() {
+                return Err(PostPollError::InvalidConfiguration("points must not be empty".to_string()));
+            }
+        } else if request.configuration.method == TabulationMethod::Positional {
+            return Err(PostPollError::InvalidConfiguration("positional method requires points".to_string()));
+        }
+
+        if let Some(order) = &request.configuration.tie_break_order {
+            if let Some(duplicate) = util::first_duplicate(order.iter()) {
+                return Err(PostPollError::InvalidConfiguration(format!("Duplicate tie break candidate: [{}]", duplicate)));
+            }
+            if let Some(unknown) = order.iter().find(|name| !request.candidates.iter().any(|c| &c.name == *name)) {
+                return Err(PostPollError::InvalidConfiguration(format!("Unknown tie break candidate: [{}]", unknown)));
+            }
+        } else if request.configuration.tie_break == TieBreak::Owner {
+            return Err(PostPollError::InvalidConfiguration("owner tie break requires tieBreakOrder".to_string()));
+        }
+
+        let now = Utc::now();
+        let expires = request.expires.unwrap_or(now + self.expiry_limits.default);
+        if expires < now + self.expiry_limits.min || expires > now + self.expiry_limits.max {
+            return Err(PostPollError::InvalidSchedule(format!(
+                "expires must be between {} and {} hours from now",
+                self.expiry_limits.min.num_hours(),
+                self.expiry_limits.max.num_hours(),
+            )));
+        }
+        if let Some(close) = request.close {
+            if close <= now || close > expires {
+                return Err(PostPollError::InvalidSchedule("close must be in the future and no later than expires".to_string()));
+            }
+        }
+
+        let poll_id: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(10)
+        .collect();
+        let seed = thread_rng().gen::<u32>() as i64;
+
+        let owner_id = identity.owner_id();
+
+        let mut transaction = self.db.new_transaction().await?;
+
+        let poll = db::Poll {
+            id: poll_id.to_owned(),
+            name: request.name.clone(),
+            description: request.description.clone(),
+            owner_id: owner_id.to_string(),
+            expires,
+            close: request.close,
+            write_ins: request.configuration.write_ins,
+            moderate_write_ins: request.configuration.moderate_write_ins,
+            write_ins_per_identity: request.configuration.write_ins_per_identity.map(|limit| limit as i16),
+            private: request.configuration.private,
+            hide_ballots: request.configuration.hide_ballots,
+            require_oidc: request.configuration.require_oidc,
+            seats: request.configuration.seats as i16,
+            unranked_scoring: request.configuration.unranked_scoring.as_str().to_string(),
+            points: request.configuration.points.as_ref()
+                .map(|points| points.iter().map(|&p| p as i32).collect()),
+            method: request.configuration.method.as_str().to_string(),
+            tie_break: request.configuration.tie_break.as_str().to_string(),
+            tie_break_order: request.configuration.tie_break_order.clone(),
+            seed,
+        };
+
+        transaction.insert_poll(&poll).await?;
+
+        for c in request.candidates.iter() {
+            transaction.insert_candidate(&poll_id, &c.name, &c.description, false, None).await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(PostPollResponse {
+            poll: Poll {
+                id: poll_id,
+                name: request.name.clone(),
+                description: request.description.clone(),
+                expires: poll.expires,
+                close: poll.close,
+                configuration: request.configuration.clone(),
+                candidates: request.candidates.clone(),
+                seed,
+            }
+        })
+    }
+
+    async fn post_candidate(&self, poll_id: &str, identity: &Identity, request: &Candidate) -> Result<(), PostCandidateError> {
+        let submitter_id = identity.owner_id();
+
+        let mut transaction = self.db.new_transaction()
+        .await?;
+
+        // Locking the poll serializes write-ins, so concurrent submissions can't both pass
+        // the per-identity limit.
+        let poll = transaction.select_poll_for_update(poll_id)
+        .await?
+        .ok_or(PostCandidateError::PollNotFound)?;
+
+        if is_closed(&poll) {
+            return Err(PostCandidateError::PollClosed);
+        }
+
+        if !poll.write_ins {
+            return Err(PostCandidateError::NoWriteIns);
+        }
+
+        if poll.require_oidc && !matches!(identity, Identity::Oidc(_)) {
+            return Err(PostCandidateError::OidcRequired);
+        }
+
+        let existing_candidates = transaction.select_candidates(&poll.id)
+        .await?;
+
+        // Pending write-ins aren't public, so a clash with one doesn't name the candidate.
+        if let Some(existing) = existing_candidates.iter().find(|e| e.name == request.name) {
+            return Err(if existing.pending {
+                PostCandidateError::NameUnavailable
+            } else {
+                PostCandidateError::DuplicateCandidate(request.name.clone())
+            });
+        }
+
+        if let Some(limit) = poll.write_ins_per_identity {
+            let submitted = existing_candidates.iter()
+                .filter(|c| c.submitter_id.as_deref() == Some(submitter_id))
+                .count();
+            if submitted >= limit as usize {
+                return Err(PostCandidateError::WriteInLimitReached);
+            }
+        }
+
+        transaction.insert_candidate(
+            poll_id,
+            &request.name,
+            &request.description,
+            poll.moderate_write_ins,
+            Some(submitter_id),
+        ).await?;
+        transaction.commit().await?;
+        
+        Ok(())
+    }
+
+    /// Withdrawing a candidate keeps the rankings already cast for them, but leaves them
+    /// out of the count and off new ballots.
+    async fn update_candidate(&self,
+        poll_id: &str,
+        identity: &Identity,
+        candidate_name: &str,
+        request: &PatchCandidateRequest
+    ) -> Result<(), UpdateCandidateError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let mut poll = tx.select_poll(poll_id).await?
+            .ok_or(UpdateCandidateError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(UpdateCandidateError::NotOwner);
+        }
+
+        if is_closed(&poll) {
+            return Err(UpdateCandidateError::PollClosed);
+        }
+
+        let candidates = tx.select_candidates(poll_id).await?;
+
+        if let Some(name) = &request.name {
+            if name != candidate_name && candidates.iter().any(|c| &c.name == name) {
+                return Err(UpdateCandidateError::DuplicateCandidate(name.clone()));
+            }
+        }
+
+        let mut candidate = candidates.into_iter()
+            .find(|c| c.name == candidate_name)
+            .ok_or(UpdateCandidateError::CandidateNotFound)?;
+
+        // Renaming a ranked candidate would change what those ballots voted for.
+        if request.name.as_deref().is_some_and(|name| name != candidate_name)
+            && tx.count_rankings_for_candidate(candidate.id).await? > 0 {
+            return Err(UpdateCandidateError::LockedByBallots);
+        }
+
+        if let Some(name) = &request.name {
+            if let Some(order) = poll.tie_break_order.as_mut() {
+                for entry in order.iter_mut().filter(|entry| entry.as_str() == candidate_name) {
+                    *entry = name.clone();
+                }
+                tx.update_poll_tie_break_order(poll_id, &poll.tie_break_order).await?;
+            }
+            candidate.name = name.clone();
+        }
+        if let Some(description) = &request.description {
+            candidate.description = description.clone();
+        }
+        if let Some(withdrawn) = request.withdrawn {
+            candidate.withdrawn = withdrawn;
+        }
+
+        tx.update_candidate(&candidate).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_pending_candidates(&self, poll_id: &str, identity: &Identity) -> Result<GetCandidatesResponse, ModerateCandidateError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        tx.select_poll(poll_id).await?
+            .ok_or(ModerateCandidateError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(ModerateCandidateError::NotOwner);
+        }
+
+        let candidates = tx.select_candidates(poll_id).await?
+            .into_iter()
+            .filter(|c| c.pending)
+            .map(|c| Candidate {
+                name: c.name,
+                description: c.description,
+                withdrawn: c.withdrawn,
+            })
+            .collect();
+
+        Ok(GetCandidatesResponse {
+            candidates,
+        })
+    }
+
+    async fn get_write_ins(&self, poll_id: &str, identity: &Identity) -> Result<GetWriteInsResponse, ModerateCandidateError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        tx.select_poll(poll_id).await?
+            .ok_or(ModerateCandidateError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(ModerateCandidateError::NotOwner);
+        }
+
+        let ballot_names: HashMap<String, String> = tx.select_ballots(poll_id).await?
+            .into_iter()
+            .map(|b| (b.owner_id, b.name))
+            .collect();
+
+        let write_ins = tx.select_candidates(poll_id).await?
+            .into_iter()
+            .filter_map(|c| {
+                let submitter_id = c.submitter_id?;
+                Some(WriteIn {
+                    name: c.name,
+                    description: c.description,
+                    pending: c.pending,
+                    withdrawn: c.withdrawn,
+                    submitter: ballot_names.get(&submitter_id).cloned(),
+                    submitter_id,
+                })
+            })
+            .collect();
+
+        Ok(GetWriteInsResponse {
+            write_ins,
+        })
+    }
+
+    /// Creates a single-use invitation token for each invitee, returning only the new ones.
+    async fn post_invitations(&self,
+        poll_id: &str,
+        identity: &Identity,
+        request: &PostInvitationsRequest,
+    ) -> Result<GetInvitationsResponse, InvitationError> {
+        if let Some(duplicate) = util::first_duplicate(request.invitees.iter()) {
+            return Err(InvitationError::DuplicateInvitee(duplicate.clone()));
+        }
+
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(InvitationError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(InvitationError::NotOwner);
+        }
+        if !poll.private {
+            return Err(InvitationError::NotPrivate);
+        }
+
+        let existing = tx.select_invitations(poll_id).await?;
+        if let Some(duplicate) = request.invitees.iter().find(|i| existing.iter().any(|e| &e.invitee == *i)) {
+            return Err(InvitationError::DuplicateInvitee(duplicate.clone()));
+        }
+
+        let mut invitations = Vec::with_capacity(request.invitees.len());
+        for invitee in &request.invitees {
+            let invitation = db::Invitation {
+                token: thread_rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(24)
+                    .collect(),
+                invitee: invitee.clone(),
+                redeemed_by: None,
+            };
+            tx.insert_invitation(poll_id, &invitation).await?;
+            invitations.push(Invitation {
+                invitee: invitation.invitee,
+                token: invitation.token,
+                redeemed: false,
+            });
+        }
+
+        tx.commit().await?;
+
+        Ok(GetInvitationsResponse {
+            invitations,
+        })
+    }
+
+    async fn get_invitations(&self, poll_id: &str, identity: &Identity) -> Result<GetInvitationsResponse, InvitationError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        tx.select_poll(poll_id).await?
+            .ok_or(InvitationError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(InvitationError::NotOwner);
+        }
+
+        let invitations = tx.select_invitations(poll_id).await?
+            .into_iter()
+            .map(|i| Invitation {
+                invitee: i.invitee,
+                token: i.token,
+                redeemed: i.redeemed_by.is_some(),
+            })
+            .collect();
+
+        Ok(GetInvitationsResponse {
+            invitations,
+        })
+    }
+
+    /// Binds an invitation to the caller, who may then cast one ballot in the poll.
+    /// Redeeming the same invitation again as the same identity is a no-op.
+    async fn redeem_invitation(&self, poll_id: &str, identity: &Identity, token: &str) -> Result<(), RedeemInvitationError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        tx.select_poll(poll_id).await?
+            .ok_or(RedeemInvitationError::PollNotFound)?;
+
+        let invitation = tx.select_invitation(poll_id, token).await?
+            .ok_or(RedeemInvitationError::InvitationNotFound)?;
+
+        match invitation.redeemed_by {
+            Some(redeemed_by) if redeemed_by == owner_id => return Ok(()),
+            Some(_) => return Err(RedeemInvitationError::AlreadyRedeemed),
+            None => (),
+        }
+
+        if tx.select_invitation_by_redeemer(poll_id, owner_id).await?.is_some() {
+            return Err(RedeemInvitationError::AlreadyInvited);
+        }
+
+        if tx.redeem_invitation(token, owner_id).await?.rows_affected() == 0 {
+            return Err(RedeemInvitationError::AlreadyRedeemed);
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Approving a pending write-in puts it on the ballot; rejecting it deletes it.
+    async fn moderate_candidate(&self,
+        poll_id: &str,
+        identity: &Identity,
+        candidate_name: &str,
+        approve: bool
+    ) -> Result<(), ModerateCandidateError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(ModerateCandidateError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(ModerateCandidateError::NotOwner);
+        }
+
+        if is_closed(&poll) {
+            return Err(ModerateCandidateError::PollClosed);
+        }
+
+        let mut candidate = tx.select_candidates(poll_id).await?
+            .into_iter()
+            .find(|c| c.pending && c.name == candidate_name)
+            .ok_or(ModerateCandidateError::CandidateNotFound)?;
+
+        if approve {
+            candidate.pending = false;
+            tx.update_candidate(&candidate).await?;
+        } else {
+            tx.delete_candidate(candidate.id).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_admins(&self, poll_id: &str, identity: &Identity) -> Result<GetAdminsResponse, PollAdminError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        tx.select_poll(poll_id).await?
+            .ok_or(PollAdminError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(PollAdminError::NotOwner);
+        }
+
+        Ok(GetAdminsResponse {
+            admins: tx.select_poll_admins(poll_id).await?,
+        })
+    }
+
+    /// Admins may do everything the owner can except delete the poll and manage admins.
+    async fn add_admin(&self, poll_id: &str, identity: &Identity, admin_id: &str) -> Result<(), PollAdminError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(PollAdminError::PollNotFound)?;
+
+        if poll.owner_id != owner_id {
+            return Err(PollAdminError::NotOwner);
+        }
+
+        if admin_id != poll.owner_id {
+            tx.insert_poll_admin(poll_id, admin_id).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn remove_admin(&self, poll_id: &str, identity: &Identity, admin_id: &str) -> Result<(), PollAdminError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(PollAdminError::PollNotFound)?;
+
+        if poll.owner_id != owner_id {
+            return Err(PollAdminError::NotOwner);
+        }
+
+        if tx.delete_poll_admin(poll_id, admin_id).await?.rows_affected() == 0 {
+            return Err(PollAdminError::AdminNotFound);
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// The caller's own polls, soonest to expire first.
+    async fn list_polls(&self,
+        identity: &Identity,
+        cursor: Option<String>,
+        limit: Option<u16>,
+    ) -> Result<ListPollsResponse, ListPollsError> {
+        let after = match cursor {
+            Some(cursor) => Some(util::Cursor::decode(&cursor).ok_or(ListPollsError::InvalidCursor)?),
+            None => None,
+        };
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE) as usize;
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let mut summaries = tx.select_polls_by_owner(
+            identity.owner_id(),
+            after.as_ref().map(|c| (c.timestamp, c.id.as_str())),
+            limit as i64 + 1,
+        ).await?;
+
+        let next_cursor = if summaries.len() > limit {
+            summaries.truncate(limit);
+            summaries.last().map(|last| util::Cursor {
+                timestamp: last.expires,
+                id: last.id.clone(),
+            }.encode())
+        } else {
+            None
+        };
+
+        let polls = summaries.into_iter()
+            .map(|s| PollSummary {
+                closed: has_closed(s.expires, s.close),
+                id: s.id,
+                name: s.name,
+                ballots: s.ballots,
+                close: s.close,
+                expires: s.expires,
+            })
+            .collect();
+
+        Ok(ListPollsResponse {
+            polls,
+            next_cursor,
+        })
+    }
+
+    /// The caller's ballots across all polls, most recently cast first.
+    async fn get_my_ballots(&self, identity: &Identity) -> Result<GetMyBallotsResponse, GetMyBallotsError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let ballots = tx.select_ballots_by_owner(owner_id).await?;
+        let mut rankings_by_ballot: HashMap<(String, String), Vec<String>> = tx.select_rankings_by_owner(owner_id)
+            .await?
+            .into_iter()
+            .map(|r| ((r.poll_id, r.ballot_id), r.candidate))
+            .into_group_map();
+
+        let ballots = ballots.into_iter()
+            .map(|b| MyBallot {
+                rankings: rankings_by_ballot
+                    .remove(&(b.poll_id.clone(), b.id.clone()))
+                    .unwrap_or_default(),
+                closed: has_closed(b.expires, b.close),
+                poll_id: b.poll_id,
+                poll_name: b.poll_name,
+                id: b.id,
+                name: b.name,
+                timestamp: b.timestamp,
+            })
+            .collect();
+
+        Ok(GetMyBallotsResponse {
+            ballots,
+        })
+    }
+
+    /// The poll's ballots, oldest first, a page at a time.
+    async fn list_ballots(&self, poll_id: &str, query: &ListBallotsQuery) -> Result<ListBallotsResponse, ListBallotsError> {
+        let after = match &query.cursor {
+            Some(cursor) => Some(util::Cursor::decode(cursor).ok_or(ListBallotsError::InvalidCursor)?),
+            None => None,
+        };
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE) as usize;
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(ListBallotsError::PollNotFound)?;
+
+        if poll.hide_ballots {
+            return Err(ListBallotsError::BallotsHidden);
+        }
+
+        let mut ballots = tx.select_ballots_page(
+            poll_id,
+            after.as_ref().map(|c| (c.timestamp, c.id.as_str())),
+            query.from,
+            query.to,
+            limit as i64 + 1,
+        ).await?;
+
+        let next_cursor = if ballots.len() > limit {
+            ballots.truncate(limit);
+            ballots.last().map(|last| util::Cursor {
+                timestamp: last.timestamp,
+                id: last.id.clone(),
+            }.encode())
+        } else {
+            None
+        };
+
+        let ballot_ids: Vec<String> = ballots.iter().map(|b| b.id.clone()).collect();
+        let mut rankings_by_ballot_id: HashMap<String, Vec<Arc<String>>> = tx.select_rankings_for_ballots(poll_id, &ballot_ids)
+            .await?
+            .into_iter()
+            .map(|r| (r.ballot_id, Arc::new(r.candidate)))
+            .into_group_map();
+
+        let ballots = ballots.into_iter()
+            .map(|b| BallotSummary {
+                rankings: rankings_by_ballot_id.remove(&b.id).unwrap_or_default(),
+                id: b.id,
+                timestamp: b.timestamp,
+                name: Arc::new(b.name),
+            })
+            .collect();
+
+        Ok(ListBallotsResponse {
+            ballots,
+            next_cursor,
+        })
+    }
+
+    async fn get_poll(&self, id: &str, include_ballots: bool) -> Result<GetPollResponse, GetPollError> {
+        self.load_poll(id, |poll| include_ballots && !poll.hide_ballots).await
+    }
+
+    async fn get_results(&self, poll_id: &str, method: Option<TabulationMethod>) -> Result<GetResultsResponse, GetResultsError> {
+        let poll = self.load_poll(poll_id, |_| true).await?;
+        let election = tabulation::Election::from(&poll);
+        let configuration = &poll.poll.configuration;
+        let method = method.unwrap_or(configuration.method);
+        let mut tie_breaker = TieBreaker::new(
+            &election,
+            configuration.tie_break,
+            poll.poll.seed,
+            configuration.tie_break_order.as_deref().unwrap_or_default(),
+        );
+        let unranked = configuration.unranked_scoring;
+
+        let results = match method {
+            TabulationMethod::Irv => Results::Irv(tabulation::irv(&election, &mut tie_breaker)),
+            TabulationMethod::Schulze => Results::Schulze(tabulation::schulze(&election, &mut tie_breaker)),
+            TabulationMethod::RankedPairs => Results::RankedPairs(tabulation::ranked_pairs(&election, &mut tie_breaker)),
+            TabulationMethod::Stv => {
+                let seats = configuration.seats as usize;
+                Results::Stv(tabulation::stv(&election, seats, &mut tie_breaker))
+            },
+            TabulationMethod::Borda => Results::Borda(
+                tabulation::scoring(&election, Positional::Borda, unranked, &mut tie_breaker)
+            ),
+            TabulationMethod::Dowdall => Results::Dowdall(
+                tabulation::scoring(&election, Positional::Dowdall, unranked, &mut tie_breaker)
+            ),
+            TabulationMethod::Positional => {
+                let points = configuration.points.as_ref()
+                    .ok_or_else(|| GetResultsError::InvalidMethod("Poll has no points configured".to_string()))?;
+                Results::Positional(
+                    tabulation::scoring(&election, Positional::Custom(points), unranked, &mut tie_breaker)
+                )
+            },
+        };
+
+        Ok(GetResultsResponse {
+            official: method == configuration.method,
+            results,
+            ties: tie_breaker.into_ties(),
+        })
+    }
+
+    async fn close_poll(&self, poll_id: &str, identity: &Identity) -> Result<(), ClosePollError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(ClosePollError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(ClosePollError::NotOwner);
+        }
+
+        if is_closed(&poll) {
+            return Err(ClosePollError::AlreadyClosed);
+        }
+
+        tx.update_poll_close(poll_id, Utc::now()).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Once a ballot has been cast, only the description and close time may change, so
+    /// no voter's ballot ends up attached to a different question.
+    async fn update_poll(&self, poll_id: &str, identity: &Identity, request: &PatchPollRequest) -> Result<(), UpdatePollError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let mut poll = tx.select_poll(poll_id).await?
+            .ok_or(UpdatePollError::PollNotFound)?;
+
+        if !tx.is_poll_admin(poll_id, owner_id).await? {
+            return Err(UpdatePollError::NotOwner);
+        }
+
+        if is_closed(&poll) {
+            return Err(UpdatePollError::PollClosed);
+        }
+
+        if tx.count_ballots(poll_id).await? > 0 {
+            if request.name.is_some() {
+                return Err(UpdatePollError::LockedByBallots("name"));
+            }
+            if request.write_ins.is_some() {
+                return Err(UpdatePollError::LockedByBallots("writeIns"));
+            }
+        }
+
+        if let Some(close) = request.close {
+            if let Some(close) = close {
+                if close <= Utc::now() || close > poll.expires {
+                    return Err(UpdatePollError::InvalidSchedule("close must be in the future and no later than expires".to_string()));
+                }
+            }
+            poll.close = close;
+        }
+        if let Some(name) = &request.name {
+            poll.name = name.clone();
+        }
+        if let Some(description) = &request.description {
+            poll.description = description.clone();
+        }
+        if let Some(write_ins) = request.write_ins {
+            poll.write_ins = write_ins;
+        }
+
+        tx.update_poll(&poll).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Only the owner may delete a poll; admins can close it instead.
+    async fn delete_poll(&self, poll_id: &str, identity: &Identity) -> Result<(), DeletePollError> {
+        let owner_id = identity.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(DeletePollError::PollNotFound)?;
+
+        if poll.owner_id != owner_id {
+            return Err(DeletePollError::NotOwner);
+        }
+
+        tx.delete_polls(&[poll.id]).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
     async fn put_ballot(&self,
         poll_id: &str,
@@ -276,16 +1297,39 @@ impl PollOperationsT for PollOperations {
             return Err(PutBallotError::DuplicateRanking(duplicate.clone()));
         }
 
-        let Identity::SecretKey(owner_id) = user_id;
+        let owner_id = user_id.owner_id();
 
         let mut tx = self.db.new_transaction().await?;
 
-        /*poll exists?*/ tx.select_poll(poll_id).await?
+        let mut poll = tx.select_poll(poll_id).await?
         .ok_or(PutBallotError::PollNotFound)?;
 
+        // Locking a private poll serializes its ballots, so concurrent requests from one
+        // invitee can't both pass the one-ballot check.
+        if poll.private {
+            poll = tx.select_poll_for_update(poll_id).await?
+            .ok_or(PutBallotError::PollNotFound)?;
+        }
+
+        if is_closed(&poll) {
+            return Err(PutBallotError::PollClosed);
+        }
+
+        if poll.require_oidc && !matches!(user_id, Identity::Oidc(_)) {
+            return Err(PutBallotError::OidcRequired);
+        }
+
+        if poll.private && tx.select_invitation_by_redeemer(poll_id, owner_id).await?.is_none() {
+            return Err(PutBallotError::NotInvited);
+        }
+
         let previous_row = tx.select_ballot(poll_id, ballot_id)
         .await?;
 
+        if poll.private && previous_row.is_none() && tx.count_ballots_by_owner(poll_id, owner_id).await? > 0 {
+            return Err(PutBallotError::AlreadyVoted);
+        }
+
         let ballot = db::Ballot {
             id: String::from(ballot_id),
             name: request.name.clone(),
@@ -316,6 +1360,62 @@ impl PollOperationsT for PollOperations {
         Ok(())
     }
 
+    /// Anyone may read a ballot unless the poll hides ballots, in which case only the
+    /// identity that cast it may.
+    async fn get_ballot(&self,
+        poll_id: &str,
+        user_id: &Option<Identity>,
+        ballot_id: &str,
+    ) -> Result<BallotSummary, GetBallotError> {
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(GetBallotError::PollNotFound)?;
+
+        let ballot = tx.select_ballot(poll_id, ballot_id).await?
+            .ok_or(GetBallotError::BallotNotFound)?;
+
+        if poll.hide_ballots && user_id.as_ref().map(|id| id.owner_id()) != Some(ballot.owner_id.as_str()) {
+            return Err(GetBallotError::NotOwner);
+        }
+
+        let rankings = tx.select_ballot_rankings(poll_id, ballot_id).await?
+            .into_iter()
+            .map(|r| Arc::new(r.candidate))
+            .collect();
+
+        Ok(BallotSummary {
+            id: ballot.id,
+            timestamp: ballot.timestamp,
+            name: Arc::new(ballot.name),
+            rankings,
+        })
+    }
+
+    async fn delete_ballot(&self, poll_id: &str, user_id: &Identity, ballot_id: &str) -> Result<(), DeleteBallotError> {
+        let owner_id = user_id.owner_id();
+
+        let mut tx = self.db.new_transaction().await?;
+
+        let poll = tx.select_poll(poll_id).await?
+            .ok_or(DeleteBallotError::PollNotFound)?;
+
+        if is_closed(&poll) {
+            return Err(DeleteBallotError::PollClosed);
+        }
+
+        let ballot = tx.select_ballot(poll_id, ballot_id).await?
+            .ok_or(DeleteBallotError::BallotNotFound)?;
+
+        if ballot.owner_id != owner_id {
+            return Err(DeleteBallotError::NotOwner);
+        }
+
+        tx.delete_ballot(poll_id, ballot_id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
 
     async fn insert_rankings<'a>(&self,
         tx: &mut PickyPollTransaction<'a>,
@@ -326,15 +1426,19 @@ impl PollOperationsT for PollOperations {
         let candidates = tx.select_candidates(poll_id)
         .await?;
 
-        let mut candidate_name_to_id: HashMap<String, i32> = candidates
+        let mut candidate_name_to_id: HashMap<String, (i32, bool)> = candidates
             .into_iter()
-            .map(|c| (c.name, c.id))
+            .filter(|c| !c.pending)
+            .map(|c| (c.name, (c.id, c.withdrawn)))
             .collect();
         
         for (i, candidate_name) in rankings.iter().enumerate() {
-            let candidate_id = candidate_name_to_id
+            let (candidate_id, withdrawn) = candidate_name_to_id
                 .remove(candidate_name)
                 .ok_or_else(|| PutBallotError::CandidateNotFound(candidate_name.clone()))?;
+            if withdrawn {
+                return Err(PutBallotError::CandidateWithdrawn(candidate_name.clone()));
+            }
             let row = db::Ranking {
                 poll_id: String::from(poll_id),
                 ballot_id: String::from(ballot_id),
@@ -358,22 +1462,23 @@ mod tests {
     #[tokio::test]
     async fn test_post_poll() {
         let db = PickyDb::new(test_db::new_pool().await);
-        let service = PollOperations::new(db);
+        let service = PollOperations::new(db, ExpiryLimits::default());
 
         let mock_user = Identity::SecretKey("test user".to_string());
 
         let post_poll_request = PostPollRequest {
             name: "test poll name".to_owned(),
             description: Some("test poll description".to_owned()),
+            expires: None,
+            close: None,
             candidates: vec!(
                 Candidate{
                     name: "candidate".to_owned(),
                     description: Some("candidate description".to_owned()),
+                    withdrawn: false,
                 }
             ),
-            configuration: Configuration {
-                write_ins: false,
-            },
+            configuration: Configuration::default(),
         };
         let post_poll_response = service
             .post_poll(&mock_user, &post_poll_request)
@@ -381,7 +1486,7 @@ mod tests {
             .unwrap();
 
         let get_poll_response = service
-            .get_poll(&post_poll_response.poll.id)
+            .get_poll(&post_poll_response.poll.id, true)
             .await
             .unwrap();
 
@@ -394,35 +1499,618 @@ mod tests {
         assert_eq!(post_poll_request.candidates, response_candidates);
     }
 
+    #[tokio::test]
+    async fn test_configuration_round_trip() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let service = PollOperations::new(db, ExpiryLimits::default());
+
+        let configuration = Configuration {
+            write_ins: true,
+            seats: 2,
+            unranked_scoring: UnrankedScoring::Average,
+            points: Some(vec!(5, 3, 1)),
+            method: TabulationMethod::Positional,
+            tie_break: TieBreak::Owner,
+            tie_break_order: Some(vec!("🍦".to_string())),
+            moderate_write_ins: true,
+            ..Configuration::default()
+        };
+        let post_poll_request = PostPollRequest {
+            name: "test poll name".to_owned(),
+            description: Some("test poll description".to_owned()),
+            expires: None,
+            close: None,
+            candidates: vec!(Candidate { name: "🍦".to_string(), description: None, withdrawn: false }),
+            configuration: configuration.clone(),
+        };
+        let post_poll_response = service
+            .post_poll(&Identity::SecretKey("test user".to_string()), &post_poll_request)
+            .await
+            .unwrap();
+
+        let get_poll_response = service
+            .get_poll(&post_poll_response.poll.id, true)
+            .await
+            .unwrap();
+
+        assert_eq!(configuration, get_poll_response.poll.configuration);
+    }
+
+    #[tokio::test]
+    async fn test_moderate_write_ins() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+
+        let mut post_poll_request = test_put_ballot::mock_poll_request();
+        post_poll_request.configuration.write_ins = true;
+        post_poll_request.configuration.moderate_write_ins = true;
+        let poll_id = ops.post_poll(&owner, &post_poll_request).await
+            .expect("post poll should succeed")
+            .poll
+            .id;
+
+        for name in ["pie", "mud", "tart"] {
+            let write_in = Candidate { name: name.to_string(), description: None, withdrawn: false };
+            ops.post_candidate(&poll_id, &owner, &write_in).await
+                .expect("post candidate should succeed");
+        }
+
+        let get_poll_response = ops.get_poll(&poll_id, true).await
+            .expect("get poll should succeed");
+        assert_eq!(3, get_poll_response.poll.candidates.len());
+
+        let not_owner = Identity::SecretKey("not the owner".to_string());
+        let result = ops.get_pending_candidates(&poll_id, &not_owner).await;
+        assert!(matches!(result, Err(ModerateCandidateError::NotOwner)));
+        let pending = ops.get_pending_candidates(&poll_id, &owner).await
+            .expect("owner should see pending candidates");
+        assert_eq!(3, pending.candidates.len());
+
+        let pie = Candidate { name: "pie".to_string(), description: None, withdrawn: false };
+        let result = ops.post_candidate(&poll_id, &not_owner, &pie).await;
+        assert!(matches!(result, Err(PostCandidateError::NameUnavailable)));
+
+        ops.moderate_candidate(&poll_id, &owner, "pie", true).await
+            .expect("approve should succeed");
+        ops.moderate_candidate(&poll_id, &owner, "mud", false).await
+            .expect("reject should succeed");
+
+        let get_poll_response = ops.get_poll(&poll_id, true).await
+            .expect("get poll should succeed");
+        let names: Vec<&str> = get_poll_response.poll.candidates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(vec!("cookies", "cake", "ice cream", "pie"), names);
+
+        ops.close_poll(&poll_id, &owner).await
+            .expect("close should succeed");
+        let result = ops.moderate_candidate(&poll_id, &owner, "tart", true).await;
+        assert!(matches!(result, Err(ModerateCandidateError::PollClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_list_polls() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        // The test database isn't reset between runs, so each run lists a fresh owner's polls.
+        let owner_id: String = thread_rng().sample_iter(&Alphanumeric).take(10).collect();
+        let owner = Identity::SecretKey(owner_id);
+
+        let mut poll_ids = Vec::new();
+        for hours in [30, 10, 20].iter() {
+            let mut request = test_put_ballot::mock_poll_request();
+            request.expires = Some(Utc::now() + Duration::hours(*hours));
+            let poll_id = ops.post_poll(&owner, &request).await
+                .expect("post poll should succeed")
+                .poll
+                .id;
+            poll_ids.push(poll_id);
+        }
+        let ballot = PutBallotRequest {
+            name: "voter".to_string(),
+            rankings: vec!("cake".to_string()),
+        };
+        ops.put_ballot(&poll_ids[1], &owner, "ballot", &ballot).await
+            .expect("put ballot should succeed");
+
+        let first_page = ops.list_polls(&owner, None, Some(2)).await
+            .expect("list polls should succeed");
+        let ids: Vec<&str> = first_page.polls.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(vec!(poll_ids[1].as_str(), poll_ids[2].as_str()), ids);
+        assert_eq!(1, first_page.polls[0].ballots);
+        assert!(!first_page.polls[0].closed);
+
+        let second_page = ops.list_polls(&owner, first_page.next_cursor, Some(2)).await
+            .expect("list polls should succeed");
+        let ids: Vec<&str> = second_page.polls.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(vec!(poll_ids[0].as_str()), ids);
+        assert!(second_page.next_cursor.is_none());
+
+        let result = ops.list_polls(&owner, Some("garbage".to_string()), None).await;
+        assert!(matches!(result, Err(ListPollsError::InvalidCursor)));
+    }
+
+    #[tokio::test]
+    async fn test_get_my_ballots() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let voter_id: String = thread_rng().sample_iter(&Alphanumeric).take(10).collect();
+        let voter = Identity::SecretKey(voter_id);
+
+        let first_poll = test_put_ballot::post_mock_poll(&ops).await;
+        let second_poll = test_put_ballot::post_mock_poll(&ops).await;
+        let ballot = PutBallotRequest {
+            name: "voter".to_string(),
+            rankings: vec!("cake".to_string(), "cookies".to_string()),
+        };
+        ops.put_ballot(&first_poll, &voter, "ballot", &ballot).await
+            .expect("put ballot should succeed");
+        ops.put_ballot(&second_poll, &voter, "ballot", &ballot).await
+            .expect("put ballot should succeed");
+
+        let ballots = ops.get_my_ballots(&voter).await
+            .expect("get my ballots should succeed")
+            .ballots;
+        assert_eq!(2, ballots.len());
+        assert_eq!(second_poll, ballots[0].poll_id);
+        assert_eq!(first_poll, ballots[1].poll_id);
+        for b in ballots.iter() {
+            assert_eq!(vec!("cake".to_string(), "cookies".to_string()), b.rankings);
+            assert!(!b.closed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_ballots() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let voter = Identity::SecretKey("voter".to_string());
+
+        let poll_id = test_put_ballot::post_mock_poll(&ops).await;
+        for ballot_id in ["first", "second", "third"].iter() {
+            let ballot = PutBallotRequest {
+                name: ballot_id.to_string(),
+                rankings: vec!("cake".to_string()),
+            };
+            ops.put_ballot(&poll_id, &voter, ballot_id, &ballot).await
+                .expect("put ballot should succeed");
+        }
+
+        let mut query = ListBallotsQuery { cursor: None, limit: Some(2), from: None, to: None };
+        let first_page = ops.list_ballots(&poll_id, &query).await
+            .expect("list ballots should succeed");
+        let ids: Vec<&str> = first_page.ballots.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(vec!("first", "second"), ids);
+        assert_eq!(vec!(Arc::new("cake".to_string())), first_page.ballots[0].rankings);
+
+        query.cursor = first_page.next_cursor;
+        let second_page = ops.list_ballots(&poll_id, &query).await
+            .expect("list ballots should succeed");
+        let ids: Vec<&str> = second_page.ballots.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(vec!("third"), ids);
+        assert!(second_page.next_cursor.is_none());
+
+        let query = ListBallotsQuery {
+            cursor: None,
+            limit: None,
+            from: Some(first_page.ballots[1].timestamp),
+            to: Some(second_page.ballots[0].timestamp),
+        };
+        let filtered = ops.list_ballots(&poll_id, &query).await
+            .expect("list ballots should succeed");
+        let ids: Vec<&str> = filtered.ballots.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(vec!("second"), ids);
+
+        let get_poll_response = ops.get_poll(&poll_id, false).await
+            .expect("get poll should succeed");
+        assert!(get_poll_response.ballots.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_ballot() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+        let voter = Identity::SecretKey("voter".to_string());
+
+        let mut post_poll_request = test_put_ballot::mock_poll_request();
+        post_poll_request.configuration.hide_ballots = true;
+        let poll_id = ops.post_poll(&owner, &post_poll_request).await
+            .expect("post poll should succeed")
+            .poll
+            .id;
+
+        let ballot = PutBallotRequest {
+            name: "voter".to_string(),
+            rankings: vec!("ice cream".to_string(), "cake".to_string()),
+        };
+        ops.put_ballot(&poll_id, &voter, "voter_ballot", &ballot).await
+            .expect("put ballot should succeed");
+
+        let read = ops.get_ballot(&poll_id, &Some(voter.clone()), "voter_ballot").await
+            .expect("voter should read their ballot");
+        assert_eq!(vec!(Arc::new("ice cream".to_string()), Arc::new("cake".to_string())), read.rankings);
+
+        let result = ops.get_ballot(&poll_id, &Some(owner), "voter_ballot").await;
+        assert!(matches!(result, Err(GetBallotError::NotOwner)));
+        let result = ops.get_ballot(&poll_id, &None, "voter_ballot").await;
+        assert!(matches!(result, Err(GetBallotError::NotOwner)));
+        let result = ops.get_ballot(&poll_id, &Some(voter), "missing").await;
+        assert!(matches!(result, Err(GetBallotError::BallotNotFound)));
+
+        let query = ListBallotsQuery { cursor: None, limit: None, from: None, to: None };
+        let result = ops.list_ballots(&poll_id, &query).await;
+        assert!(matches!(result, Err(ListBallotsError::BallotsHidden)));
+
+        let get_poll_response = ops.get_poll(&poll_id, true).await
+            .expect("get poll should succeed");
+        assert!(get_poll_response.ballots.is_empty());
+        let results = ops.get_results(&poll_id, Some(TabulationMethod::Irv)).await
+            .expect("get results should succeed");
+        match results.results {
+            Results::Irv(irv) => assert_eq!(Some(Arc::new("ice cream".to_string())), irv.winner),
+            _ => panic!("expected instant-runoff results"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_admins() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+        let admin = Identity::SecretKey("admin".to_string());
+
+        let poll_id = test_put_ballot::post_mock_poll(&ops).await;
+
+        let result = ops.close_poll(&poll_id, &admin).await;
+        assert!(matches!(result, Err(ClosePollError::NotOwner)));
+        let result = ops.add_admin(&poll_id, &admin, admin.owner_id()).await;
+        assert!(matches!(result, Err(PollAdminError::NotOwner)));
+
+        ops.add_admin(&poll_id, &owner, admin.owner_id()).await
+            .expect("owner should add admins");
+        let admins = ops.get_admins(&poll_id, &admin).await
+            .expect("admin should list admins")
+            .admins;
+        assert_eq!(vec!(admin.owner_id().to_string()), admins);
+
+        let result = ops.delete_poll(&poll_id, &admin).await;
+        assert!(matches!(result, Err(DeletePollError::NotOwner)));
+        ops.close_poll(&poll_id, &admin).await
+            .expect("admin should close the poll");
+
+        ops.remove_admin(&poll_id, &owner, admin.owner_id()).await
+            .expect("owner should remove admins");
+        let result = ops.remove_admin(&poll_id, &owner, admin.owner_id()).await;
+        assert!(matches!(result, Err(PollAdminError::AdminNotFound)));
+        let result = ops.get_admins(&poll_id, &admin).await;
+        assert!(matches!(result, Err(PollAdminError::NotOwner)));
+    }
+
+    #[tokio::test]
+    async fn test_private_poll() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+        let member = Identity::SecretKey("member".to_string());
+        let outsider = Identity::SecretKey("outsider".to_string());
+
+        let mut post_poll_request = test_put_ballot::mock_poll_request();
+        post_poll_request.configuration.private = true;
+        let poll_id = ops.post_poll(&owner, &post_poll_request).await
+            .expect("post poll should succeed")
+            .poll
+            .id;
+
+        let invitations = PostInvitationsRequest {
+            invitees: vec!("member@example.com".to_string()),
+        };
+        let result = ops.post_invitations(&poll_id, &member, &invitations).await;
+        assert!(matches!(result, Err(InvitationError::NotOwner)));
+        let token = ops.post_invitations(&poll_id, &owner, &invitations).await
+            .expect("owner should invite members")
+            .invitations[0]
+            .token
+            .clone();
+
+        let ballot = PutBallotRequest {
+            name: "member".to_string(),
+            rankings: vec!("cake".to_string()),
+        };
+        let result = ops.put_ballot(&poll_id, &member, "member_ballot", &ballot).await;
+        assert!(matches!(result, Err(PutBallotError::NotInvited)));
+
+        ops.redeem_invitation(&poll_id, &member, &token).await
+            .expect("redeem should succeed");
+        let result = ops.redeem_invitation(&poll_id, &outsider, &token).await;
+        assert!(matches!(result, Err(RedeemInvitationError::AlreadyRedeemed)));
+
+        ops.put_ballot(&poll_id, &member, "member_ballot", &ballot).await
+            .expect("invited member should vote");
+        let result = ops.put_ballot(&poll_id, &member, "second_ballot", &ballot).await;
+        assert!(matches!(result, Err(PutBallotError::AlreadyVoted)));
+        let result = ops.put_ballot(&poll_id, &outsider, "outsider_ballot", &ballot).await;
+        assert!(matches!(result, Err(PutBallotError::NotInvited)));
+
+        let invitations = ops.get_invitations(&poll_id, &owner).await
+            .expect("owner should list invitations")
+            .invitations;
+        assert!(invitations[0].redeemed);
+    }
+
+    #[tokio::test]
+    async fn test_private_poll_concurrent_ballots() {
+        // Separate pools, so the two ballots are cast in concurrent transactions.
+        let ops = PollOperations::new(PickyDb::new(test_db::new_pool().await), ExpiryLimits::default());
+        let other_ops = PollOperations::new(PickyDb::new(test_db::new_pool().await), ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+        let member = Identity::SecretKey("member".to_string());
+
+        let mut post_poll_request = test_put_ballot::mock_poll_request();
+        post_poll_request.configuration.private = true;
+        let ballot = PutBallotRequest {
+            name: "member".to_string(),
+            rankings: vec!("cake".to_string()),
+        };
+        let invitations = PostInvitationsRequest {
+            invitees: vec!("member@example.com".to_string()),
+        };
+
+        // The race doesn't show up on every attempt, so try a few polls.
+        for _ in 0..5 {
+            let poll_id = ops.post_poll(&owner, &post_poll_request).await
+                .expect("post poll should succeed")
+                .poll
+                .id;
+            let token = ops.post_invitations(&poll_id, &owner, &invitations).await
+                .expect("owner should invite members")
+                .invitations[0]
+                .token
+                .clone();
+            ops.redeem_invitation(&poll_id, &member, &token).await
+                .expect("redeem should succeed");
+
+            let (first, second) = futures::join!(
+                ops.put_ballot(&poll_id, &member, "first_ballot", &ballot),
+                other_ops.put_ballot(&poll_id, &member, "second_ballot", &ballot),
+            );
+            let results = [first, second];
+            assert_eq!(1, results.iter().filter(|r| r.is_ok()).count());
+            assert!(results.iter().any(|r| matches!(r, Err(PutBallotError::AlreadyVoted))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_require_oidc() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+        let secret_voter = Identity::SecretKey("voter".to_string());
+        let oidc_voter = Identity::Oidc("oidc:employee".to_string());
+
+        let mut post_poll_request = test_put_ballot::mock_poll_request();
+        post_poll_request.configuration.write_ins = true;
+        post_poll_request.configuration.require_oidc = true;
+        let poll_id = ops.post_poll(&owner, &post_poll_request).await
+            .expect("post poll should succeed")
+            .poll
+            .id;
+
+        let ballot = PutBallotRequest {
+            name: "voter".to_string(),
+            rankings: vec!("cake".to_string()),
+        };
+        let result = ops.put_ballot(&poll_id, &secret_voter, "secret_ballot", &ballot).await;
+        assert!(matches!(result, Err(PutBallotError::OidcRequired)));
+        ops.put_ballot(&poll_id, &oidc_voter, "oidc_ballot", &ballot).await
+            .expect("OIDC voter should vote");
+
+        let pie = Candidate { name: "pie".to_string(), description: None, withdrawn: false };
+        let result = ops.post_candidate(&poll_id, &secret_voter, &pie).await;
+        assert!(matches!(result, Err(PostCandidateError::OidcRequired)));
+        ops.post_candidate(&poll_id, &oidc_voter, &pie).await
+            .expect("OIDC voter should submit write-ins");
+    }
+
+    #[tokio::test]
+    async fn test_write_in_limit() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+        let voter = Identity::SecretKey("voter".to_string());
+
+        let mut post_poll_request = test_put_ballot::mock_poll_request();
+        post_poll_request.configuration.write_ins = true;
+        post_poll_request.configuration.write_ins_per_identity = Some(1);
+        let poll_id = ops.post_poll(&owner, &post_poll_request).await
+            .expect("post poll should succeed")
+            .poll
+            .id;
+
+        let ballot = PutBallotRequest {
+            name: "voter name".to_string(),
+            rankings: vec!("cake".to_string()),
+        };
+        ops.put_ballot(&poll_id, &voter, "voter_ballot", &ballot).await
+            .expect("put ballot should succeed");
+
+        let pie = Candidate { name: "pie".to_string(), description: None, withdrawn: false };
+        ops.post_candidate(&poll_id, &voter, &pie).await
+            .expect("first write-in should succeed");
+
+        let tart = Candidate { name: "tart".to_string(), description: None, withdrawn: false };
+        let result = ops.post_candidate(&poll_id, &voter, &tart).await;
+        assert!(matches!(result, Err(PostCandidateError::WriteInLimitReached)));
+
+        let write_ins = ops.get_write_ins(&poll_id, &owner).await
+            .expect("owner should see write-ins")
+            .write_ins;
+        assert_eq!(1, write_ins.len());
+        assert_eq!(voter.owner_id(), write_ins[0].submitter_id);
+        assert_eq!(Some("voter name".to_string()), write_ins[0].submitter);
+    }
+
+    #[tokio::test]
+    async fn test_close_poll() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+
+        let poll_id = test_put_ballot::post_mock_poll(&ops).await;
+
+        let not_owner = Identity::SecretKey("not the owner".to_string());
+        let result = ops.close_poll(&poll_id, &not_owner).await;
+        assert!(matches!(result, Err(ClosePollError::NotOwner)));
+
+        ops.close_poll(&poll_id, &owner).await
+            .expect("owner should be able to close poll");
+
+        let get_poll_response = ops.get_poll(&poll_id, true)
+            .await
+            .expect("get poll should succeed");
+        assert!(get_poll_response.closed);
+
+        let request = PutBallotRequest {
+            name: "late voter".to_string(),
+            rankings: vec!("cake".to_string()),
+        };
+        let result = ops.put_ballot(&poll_id, &not_owner, "late_ballot_id", &request).await;
+        assert!(matches!(result, Err(PutBallotError::PollClosed)));
+
+        let result = ops.close_poll(&poll_id, &owner).await;
+        assert!(matches!(result, Err(ClosePollError::AlreadyClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_post_poll_schedule() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let service = PollOperations::new(db, ExpiryLimits::default());
+
+        let mut post_poll_request = PostPollRequest {
+            name: "standup".to_owned(),
+            description: Some("Who goes first?".to_owned()),
+            expires: Some(Utc::now() + Duration::days(1)),
+            close: Some(Utc::now() + Duration::hours(1)),
+            candidates: vec!(),
+            configuration: Configuration::default(),
+        };
+        let post_poll_response = service
+            .post_poll(&Identity::SecretKey("test user".to_string()), &post_poll_request)
+            .await
+            .expect("post poll should succeed");
+        assert_eq!(post_poll_request.close, post_poll_response.poll.close);
+
+        post_poll_request.expires = Some(Utc::now() + Duration::days(365));
+        let result = service
+            .post_poll(&Identity::SecretKey("test user".to_string()), &post_poll_request)
+            .await;
+        assert!(matches!(result, Err(PostPollError::InvalidSchedule(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_poll() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+        let owner = Identity::SecretKey("secret".to_string());
+
+        let poll_id = test_put_ballot::post_mock_poll(&ops).await;
+
+        let rename = PatchPollRequest {
+            name: Some("Dessert, again".to_string()),
+            description: None,
+            write_ins: None,
+            close: None,
+        };
+        let result = ops.update_poll(&poll_id, &Identity::SecretKey("not the owner".to_string()), &rename).await;
+        assert!(matches!(result, Err(UpdatePollError::NotOwner)));
+
+        ops.update_poll(&poll_id, &owner, &rename).await
+            .expect("owner should be able to rename poll");
+        let get_poll_response = ops.get_poll(&poll_id, true).await
+            .expect("get poll should succeed");
+        assert_eq!("Dessert, again", get_poll_response.poll.name);
+
+        let ballot = PutBallotRequest {
+            name: "voter".to_string(),
+            rankings: vec!("cake".to_string()),
+        };
+        ops.put_ballot(&poll_id, &owner, "ballot_id", &ballot).await
+            .expect("put ballot should succeed");
+
+        let result = ops.update_poll(&poll_id, &owner, &rename).await;
+        assert!(matches!(result, Err(UpdatePollError::LockedByBallots("name"))));
+
+        let schedule = PatchPollRequest {
+            name: None,
+            description: None,
+            write_ins: None,
+            close: Some(Some(Utc::now() + Duration::hours(1))),
+        };
+        ops.update_poll(&poll_id, &owner, &schedule).await
+            .expect("owner should be able to set close");
+        let clear = PatchPollRequest {
+            name: None,
+            description: Some(None),
+            write_ins: None,
+            close: Some(None),
+        };
+        ops.update_poll(&poll_id, &owner, &clear).await
+            .expect("owner should be able to clear close and description");
+        let get_poll_response = ops.get_poll(&poll_id, true).await
+            .expect("get poll should succeed");
+        assert_eq!(None, get_poll_response.poll.close);
+        assert_eq!(None, get_poll_response.poll.description);
+    }
+
+    #[tokio::test]
+    async fn test_delete_poll() {
+        let db = PickyDb::new(test_db::new_pool().await);
+        let ops = PollOperations::new(db, ExpiryLimits::default());
+
+        let poll_id = test_put_ballot::post_mock_poll(&ops).await;
+
+        let result = ops.delete_poll(&poll_id, &Identity::SecretKey("not the owner".to_string())).await;
+        assert!(matches!(result, Err(DeletePollError::NotOwner)));
+
+        ops.delete_poll(&poll_id, &Identity::SecretKey("secret".to_string())).await
+            .expect("owner should be able to delete poll");
+
+        let result = ops.get_poll(&poll_id, true).await;
+        assert!(matches!(result, Err(GetPollError::NotFound)));
+    }
+
     mod test_put_ballot {
         use super::*;
         
-        async fn post_mock_poll(ops: &PollOperations) -> String {
+        pub(super) async fn post_mock_poll(ops: &PollOperations) -> String {
             
             ops.post_poll(
                 &Identity::SecretKey("secret".to_string()),
-                &PostPollRequest{
-                    name: "Dessert".to_string(),
-                    description: Some("What dessert should be served?".to_string()),
-                    candidates: vec!(
-                        Candidate{name: "cookies".to_string(), description: None},
-                        Candidate{name: "cake".to_string(), description: None},
-                        Candidate{name: "ice cream".to_string(), description: None},
-                    ),
-                    configuration: Configuration {
-                        write_ins: false,
-                    },
-                },
+                &mock_poll_request(),
             ).await
             .expect("Should post poll")
             .poll
             .id
         }
 
+        pub(super) fn mock_poll_request() -> PostPollRequest {
+            PostPollRequest{
+                name: "Dessert".to_string(),
+                description: Some("What dessert should be served?".to_string()),
+                expires: None,
+                close: None,
+                candidates: vec!(
+                    Candidate{name: "cookies".to_string(), description: None, withdrawn: false},
+                    Candidate{name: "cake".to_string(), description: None, withdrawn: false},
+                    Candidate{name: "ice cream".to_string(), description: None, withdrawn: false},
+                ),
+                configuration: Configuration::default(),
+            }
+        }
+
         #[tokio::test]
         async fn happy_path() {
             let db = PickyDb::new(test_db::new_pool().await);
-            let ops = PollOperations::new(db);
+            let ops = PollOperations::new(db, ExpiryLimits::default());
 
             //given a poll
             let mock_poll_id = post_mock_poll(&ops).await;
@@ -437,11 +2125,11 @@ mod tests {
                     "cookies".to_string(),
                 )
             };
-            ops.put_ballot(&mock_poll_id, &mock_identity, &mock_ballot_id, &mock_request).await
+            ops.put_ballot(&mock_poll_id, &mock_identity, mock_ballot_id, &mock_request).await
             .expect("put ballot should succeed");
 
             //and we get the poll back
-            let get_poll_response = ops.get_poll(&mock_poll_id)
+            let get_poll_response = ops.get_poll(&mock_poll_id, true)
             .await
             .expect("get poll should succeed");
             //then the poll should contain the mock ballot
@@ -456,7 +2144,7 @@ mod tests {
         #[tokio::test]
         async fn replace_ballot() {
             let db = PickyDb::new(test_db::new_pool().await);
-            let ops = PollOperations::new(db);
+            let ops = PollOperations::new(db, ExpiryLimits::default());
 
             //given a poll
             let mock_poll_id = post_mock_poll(&ops).await;
@@ -471,16 +2159,16 @@ mod tests {
                     "cookies".to_string(),
                 )
             };
-            ops.put_ballot(&mock_poll_id, &mock_identity, &mock_ballot_id, &mock_request).await
+            ops.put_ballot(&mock_poll_id, &mock_identity, mock_ballot_id, &mock_request).await
             .expect("put ballot should succeed");
 
             //and mock_identity replaces the ballot with different rankings
             mock_request.rankings.reverse();
-            ops.put_ballot(&mock_poll_id, &mock_identity, &mock_ballot_id, &mock_request).await
+            ops.put_ballot(&mock_poll_id, &mock_identity, mock_ballot_id, &mock_request).await
             .expect("put ballot should succeed");
 
             //then the poll should contain the updated rankings
-            let get_poll_response = ops.get_poll(&mock_poll_id)
+            let get_poll_response = ops.get_poll(&mock_poll_id, true)
             .await
             .expect("get poll should succeed");
 
@@ -491,5 +2179,130 @@ mod tests {
             assert_eq!(ballot.name.as_ref(), &mock_request.name);
             assert_eq!(&ballot.rankings.iter().map(|r| (**r).clone()).collect::<Vec<String>>(), &mock_request.rankings)
         }
+
+        #[tokio::test]
+        async fn withdrawn_candidate() {
+            let db = PickyDb::new(test_db::new_pool().await);
+            let ops = PollOperations::new(db, ExpiryLimits::default());
+
+            //given a poll with a ballot ranking cake first
+            let mock_poll_id = post_mock_poll(&ops).await;
+            let mock_identity = Identity::SecretKey("mock user".to_string());
+            let mock_request = PutBallotRequest {
+                name: "mock username".to_string(),
+                rankings: vec!("cake".to_string(), "cookies".to_string()),
+            };
+            ops.put_ballot(&mock_poll_id, &mock_identity, "mock_ballot_id", &mock_request).await
+            .expect("put ballot should succeed");
+
+            //when the owner withdraws cake
+            let withdraw = PatchCandidateRequest {
+                name: None,
+                description: None,
+                withdrawn: Some(true),
+            };
+            ops.update_candidate(&mock_poll_id, &Identity::SecretKey("secret".to_string()), "cake", &withdraw).await
+            .expect("owner should be able to withdraw candidate");
+
+            //then the ballot keeps its rankings but counts for cookies
+            let get_poll_response = ops.get_poll(&mock_poll_id, true)
+            .await
+            .expect("get poll should succeed");
+            assert_eq!(2, get_poll_response.ballots[0].rankings.len());
+
+            let results = ops.get_results(&mock_poll_id, Some(TabulationMethod::Irv))
+            .await
+            .expect("get results should succeed");
+            match results.results {
+                Results::Irv(irv) => assert_eq!(Some("cookies"), irv.winner.as_deref().map(|w| w.as_str())),
+                _ => panic!("expected irv results"),
+            }
+
+            //and new ballots can't rank cake
+            let result = ops.put_ballot(&mock_poll_id, &mock_identity, "another_ballot_id", &mock_request).await;
+            assert!(matches!(result, Err(PutBallotError::CandidateWithdrawn(_))));
+        }
+
+        #[tokio::test]
+        async fn ranked_candidate_keeps_name() {
+            let db = PickyDb::new(test_db::new_pool().await);
+            let ops = PollOperations::new(db, ExpiryLimits::default());
+            let owner = Identity::SecretKey("secret".to_string());
+
+            //given a poll with a ballot ranking cake
+            let mock_poll_id = post_mock_poll(&ops).await;
+            let mock_request = PutBallotRequest {
+                name: "mock username".to_string(),
+                rankings: vec!("cake".to_string()),
+            };
+            ops.put_ballot(&mock_poll_id, &Identity::SecretKey("mock user".to_string()), "mock_ballot_id", &mock_request).await
+            .expect("put ballot should succeed");
+
+            //when the owner renames cake, then it is rejected
+            let rename = |name: &str| PatchCandidateRequest {
+                name: Some(name.to_string()),
+                description: None,
+                withdrawn: None,
+            };
+            let result = ops.update_candidate(&mock_poll_id, &owner, "cake", &rename("pie")).await;
+            assert!(matches!(result, Err(UpdateCandidateError::LockedByBallots)));
+
+            //but unranked candidates can still be renamed
+            ops.update_candidate(&mock_poll_id, &owner, "ice cream", &rename("gelato")).await
+            .expect("owner should be able to rename an unranked candidate");
+
+            //and ranked candidates can still be described, or have their description cleared
+            let describe = |description: Option<&str>| PatchCandidateRequest {
+                name: None,
+                description: Some(description.map(str::to_string)),
+                withdrawn: None,
+            };
+            async fn cake_description(ops: &PollOperations, poll_id: &str) -> Option<String> {
+                ops.get_poll(poll_id, false).await
+                .expect("get poll should succeed")
+                .poll
+                .candidates
+                .into_iter()
+                .find(|c| c.name == "cake")
+                .and_then(|c| c.description)
+            }
+            ops.update_candidate(&mock_poll_id, &owner, "cake", &describe(Some("chocolate"))).await
+            .expect("owner should be able to describe a ranked candidate");
+            assert_eq!(Some("chocolate".to_string()), cake_description(&ops, &mock_poll_id).await);
+            ops.update_candidate(&mock_poll_id, &owner, "cake", &describe(None)).await
+            .expect("owner should be able to clear a description");
+            assert_eq!(None, cake_description(&ops, &mock_poll_id).await);
+        }
+
+        #[tokio::test]
+        async fn delete_ballot() {
+            let db = PickyDb::new(test_db::new_pool().await);
+            let ops = PollOperations::new(db, ExpiryLimits::default());
+
+            //given a poll with a ballot from mock_identity
+            let mock_poll_id = post_mock_poll(&ops).await;
+            let mock_identity = Identity::SecretKey("mock user".to_string());
+            let mock_ballot_id = "mock_ballot_id";
+            let mock_request = PutBallotRequest {
+                name: "mock username".to_string(),
+                rankings: vec!("cake".to_string()),
+            };
+            ops.put_ballot(&mock_poll_id, &mock_identity, mock_ballot_id, &mock_request).await
+            .expect("put ballot should succeed");
+
+            //when someone else tries to delete it
+            let other_identity = Identity::SecretKey("other user".to_string());
+            let result = ops.delete_ballot(&mock_poll_id, &other_identity, mock_ballot_id).await;
+            assert!(matches!(result, Err(DeleteBallotError::NotOwner)));
+
+            //then only mock_identity can
+            ops.delete_ballot(&mock_poll_id, &mock_identity, mock_ballot_id).await
+            .expect("delete ballot should succeed");
+
+            let get_poll_response = ops.get_poll(&mock_poll_id, true)
+            .await
+            .expect("get poll should succeed");
+            assert!(get_poll_response.ballots.is_empty());
+        }
     }
 }
\ No newline at end of file
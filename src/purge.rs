@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::db::PickyDb;
+
+/// How often expired polls are purged, how many are deleted per transaction, and
+/// whether to only log what would be deleted.
+#[derive(Clone, Debug)]
+pub struct PurgeSettings {
+    pub interval: Duration,
+    pub batch_size: i64,
+    pub dry_run: bool,
+}
+
+impl Default for PurgeSettings {
+    fn default() -> Self {
+        PurgeSettings {
+            interval: Duration::from_secs(60 * 60),
+            batch_size: 100,
+            dry_run: false,
+        }
+    }
+}
+
+/// Purges expired polls every `settings.interval`, forever.
+pub async fn run(db: PickyDb, settings: PurgeSettings) {
+    let mut interval = tokio::time::interval(settings.interval);
+    loop {
+        interval.tick().await;
+        loop {
+            match purge_batch(&db, &settings).await {
+                Ok(purged) if !settings.dry_run && purged.len() as i64 == settings.batch_size => continue,
+                Ok(_) => break,
+                Err(e) => {
+                    error!("Failed to purge expired polls: {:?}", e);
+                    break;
+                },
+            }
+        }
+    }
+}
+
+/// Deletes up to `settings.batch_size` expired polls, returning their ids. Their
+/// candidates, ballots and rankings are removed by the database's cascading deletes.
+pub async fn purge_batch(db: &PickyDb, settings: &PurgeSettings) -> Result<Vec<String>, sqlx::Error> {
+    let mut tx = db.new_transaction().await?;
+    let expired = tx.select_expired_poll_ids(Utc::now(), settings.batch_size).await?;
+
+    if expired.is_empty() {
+        return Ok(expired);
+    }
+
+    if settings.dry_run {
+        info!("Dry run, would purge {} expired polls: {:?}", expired.len(), expired);
+        return Ok(expired);
+    }
+
+    tx.delete_polls(&expired).await?;
+    tx.commit().await?;
+    info!("Purged {} expired polls: {:?}", expired.len(), expired);
+    Ok(expired)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::db::{self, test_db};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn purges_expired_polls() {
+        let db = PickyDb::new(test_db::new_pool().await);
+
+        let poll = db::Poll {
+            id: "expired_poll".to_string(),
+            name: "Expired".to_string(),
+            description: Some("Should be purged".to_string()),
+            owner_id: "secret".to_string(),
+            expires: Utc::now() - Duration::days(1),
+            close: None,
+            write_ins: false,
+            seats: 1,
+            unranked_scoring: "zero".to_string(),
+            points: None,
+            method: "irv".to_string(),
+            tie_break: "random".to_string(),
+            tie_break_order: None,
+            seed: 0,
+            moderate_write_ins: false,
+            write_ins_per_identity: None,
+            private: false,
+            hide_ballots: false,
+            require_oidc: false,
+        };
+        let mut tx = db.new_transaction().await.unwrap();
+        tx.insert_poll(&poll).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let dry_run = PurgeSettings {
+            dry_run: true,
+            ..PurgeSettings::default()
+        };
+        let purged = purge_batch(&db, &dry_run).await.unwrap();
+        assert!(purged.contains(&poll.id));
+
+        let purged = purge_batch(&db, &PurgeSettings::default()).await.unwrap();
+        assert!(purged.contains(&poll.id));
+
+        let mut tx = db.new_transaction().await.unwrap();
+        assert!(tx.select_poll(&poll.id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,122 @@
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+use sqlx::Done;
+
+use crate::db::PickyDb;
+use crate::oidc::OIDC_OWNER_PREFIX;
+use crate::token::TOKEN_OWNER_PREFIX;
+
+/// Marks an owner id as already hashed, so legacy raw secrets can be told apart.
+const HASH_PREFIX: &str = "hmac-sha256:";
+
+/// Name under which `migrate_legacy_secrets` is recorded in the `migration` table.
+const LEGACY_SECRETS_MIGRATION: &str = "hash_legacy_secrets";
+
+/// Hashes X-VOTE-SECRET values with a server-side key before they are used as owner
+/// ids, so the database never holds a secret that could be replayed as a header.
+#[derive(Clone)]
+pub struct SecretHasher {
+    key: Vec<u8>,
+}
+
+impl SecretHasher {
+    pub fn new(key: &[u8]) -> SecretHasher {
+        SecretHasher { key: key.to_vec() }
+    }
+
+    pub fn hash(&self, secret: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_varkey(&self.key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(secret.as_bytes());
+        format!("{}{}", HASH_PREFIX, hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+/// Replaces raw secrets left in `poll.owner_id`, `ballot.owner_id` and
+/// `candidate.submitter_id` by earlier versions with their hashes, returning how many
+/// distinct secrets were rewritten.
+///
+/// Legacy secrets are told apart by prefix: an id starting with `hmac-sha256:`, `token:`
+/// or `oidc:` is taken to be hashed or to belong to a token or OIDC user. A raw secret
+/// that happens to start with one of these is left as it is. The migration is recorded in
+/// the `migration` table and only runs once.
+pub async fn migrate_legacy_secrets(db: &PickyDb, hasher: &SecretHasher) -> Result<usize, sqlx::Error> {
+    let mut tx = db.new_transaction().await?;
+    if tx.insert_migration(LEGACY_SECRETS_MIGRATION).await?.rows_affected() == 0 {
+        return Ok(0);
+    }
+
+    let legacy = tx.select_legacy_owner_ids(&[HASH_PREFIX, TOKEN_OWNER_PREFIX, OIDC_OWNER_PREFIX]).await?;
+
+    for owner_id in &legacy {
+        tx.replace_owner_id(owner_id, &hasher.hash(owner_id)).await?;
+    }
+
+    tx.commit().await?;
+    Ok(legacy.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{distributions::Alphanumeric, thread_rng, Rng};
+    use sqlx::Executor;
+
+    use crate::db::test_db;
+
+    use super::*;
+
+    #[test]
+    fn hash_is_keyed_and_stable() {
+        let hasher = SecretHasher::new(b"key");
+
+        let hashed = hasher.hash("secret");
+        assert_eq!(hashed, hasher.hash("secret"));
+        assert!(hashed.starts_with(HASH_PREFIX));
+        assert!(!hashed.contains("secret"));
+
+        assert_ne!(hashed, hasher.hash("other secret"));
+        assert_ne!(hashed, SecretHasher::new(b"other key").hash("secret"));
+    }
+
+    #[tokio::test]
+    async fn migrates_baseline_schema() {
+        // A schema of its own, so the baseline tables don't clash with the test database's.
+        let pool = test_db::new_pool().await;
+        let schema: String = thread_rng().sample_iter(&Alphanumeric).take(10).collect();
+        let schema = format!("upgrade_{}", schema.to_lowercase());
+        pool.execute(format!("create schema {0}; set search_path to {0}", schema).as_str()).await
+            .expect("should create schema");
+
+        pool.execute(include_str!("../test-data/create-baseline.sql")).await
+            .expect("baseline schema should load");
+        pool.execute("insert into poll (id, name, description, owner_id, expires, write_ins) \
+            values ('legacy', 'Legacy', 'Made before hashing', 'raw secret', now() + interval '1 day', false); \
+            insert into ballot (id, name, timestamp, owner_id, poll_id) \
+            values ('ballot', 'voter', now(), 'raw voter secret', 'legacy')").await
+            .expect("legacy rows should insert");
+        pool.execute(include_str!("../db/upgrade.sql")).await
+            .expect("upgrade should apply");
+
+        let db = PickyDb::new(pool.clone());
+        let hasher = SecretHasher::new(b"key");
+        let migrated = migrate_legacy_secrets(&db, &hasher).await
+            .expect("migration should succeed");
+        assert_eq!(2, migrated);
+
+        let poll_owner: String = sqlx::query_scalar("select owner_id from poll where id = 'legacy'")
+            .fetch_one(&pool).await
+            .expect("poll should exist");
+        assert_eq!(hasher.hash("raw secret"), poll_owner);
+        let ballot_owner: String = sqlx::query_scalar("select owner_id from ballot where id = 'ballot'")
+            .fetch_one(&pool).await
+            .expect("ballot should exist");
+        assert_eq!(hasher.hash("raw voter secret"), ballot_owner);
+
+        let migrated = migrate_legacy_secrets(&db, &hasher).await
+            .expect("second migration should succeed");
+        assert_eq!(0, migrated);
+
+        pool.execute(format!("drop schema {} cascade", schema).as_str()).await
+            .expect("should drop schema");
+    }
+}
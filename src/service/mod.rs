@@ -1,14 +1,20 @@
 use actix_web::{HttpRequest, HttpResponse, Result, web, FromRequest, Error};
 use actix_web::dev::{Payload, PayloadStream};
+use actix_web::http::{HeaderValue, header::AUTHORIZATION};
 use actix_web::web::ServiceConfig;
+use jsonwebtoken::Algorithm;
 use std::future::{Ready, ready};
 
 use crate::model::*;
 use crate::operations::*;
+use crate::secret::SecretHasher;
+use crate::oidc::OidcValidator;
+use crate::token::TokenValidator;
 
 mod paths;
 
 const SECRET_KEY: &str = "X-VOTE-SECRET";
+const BEARER_PREFIX: &str = "Bearer ";
 
 impl FromRequest for Identity {
     type Error = Error;
@@ -16,35 +22,122 @@ impl FromRequest for Identity {
     type Config = ();
 
     fn from_request(req: &HttpRequest, _: &mut Payload<PayloadStream>) -> Self::Future {
-        let id = req.headers()
-            .get(SECRET_KEY)
-            .ok_or_else(||{
-                let msg = format!("Missing header: {}", SECRET_KEY);
-                Error::from(HttpResponse::BadRequest().body(msg))
-            }).and_then(|header_value|
-                header_value
-                    .to_str()
-                    .map_err(|_| {
-                        let msg = format!("Non-ascii header value: {}", SECRET_KEY);
-                        Error::from(HttpResponse::InternalServerError().body(msg))
-                    })
-            ).map(|secret_key| {
-                Identity::SecretKey(secret_key.to_owned())
-            });
-
-        ready(id)
+        match req.headers().get(AUTHORIZATION) {
+            Some(authorization) => ready(bearer_identity(req, authorization)),
+            None => ready(secret_identity(req)),
+        }
     }
 }
 
+fn bearer_identity(req: &HttpRequest, authorization: &HeaderValue) -> Result<Identity, Error> {
+    let token = authorization.to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+        .ok_or_else(|| {
+            let msg = format!("Expected {}: {}<token>", AUTHORIZATION, BEARER_PREFIX);
+            Error::from(HttpResponse::Unauthorized().body(msg))
+        })?;
+    let not_accepted = || Error::from(HttpResponse::Unauthorized().body("Bearer tokens are not accepted."));
+    let rejected = |e: String| {
+        debug!("Rejected bearer token: {}", e);
+        Error::from(HttpResponse::Unauthorized().body("Invalid bearer token."))
+    };
+
+    // Locally issued tokens are signed with a shared HS256 key; ID tokens from the
+    // OIDC issuer are signed with its RSA keys.
+    match jsonwebtoken::decode_header(token) {
+        Ok(header) if header.alg == Algorithm::RS256 => {
+            let validator = req.app_data::<web::Data<OidcValidator>>()
+                .ok_or_else(not_accepted)?;
+            validator.validate(token)
+                .map(Identity::Oidc)
+                .map_err(|e| rejected(e.to_string()))
+        },
+        Ok(_) => {
+            let validator = req.app_data::<web::Data<TokenValidator>>()
+                .ok_or_else(not_accepted)?;
+            validator.validate(token)
+                .map(Identity::Token)
+                .map_err(|e| rejected(e.to_string()))
+        },
+        Err(e) => Err(rejected(e.to_string())),
+    }
+}
+
+fn secret_identity(req: &HttpRequest) -> Result<Identity, Error> {
+    let hasher = req.app_data::<web::Data<SecretHasher>>()
+        .ok_or_else(|| {
+            error!("No SecretHasher configured for the app");
+            Error::from(HttpResponse::InternalServerError().finish())
+        })?;
+    req.headers()
+        .get(SECRET_KEY)
+        .ok_or_else(||{
+            let msg = format!("Missing header: {}", SECRET_KEY);
+            Error::from(HttpResponse::BadRequest().body(msg))
+        }).and_then(|header_value|
+            header_value
+                .to_str()
+                .map_err(|_| {
+                    let msg = format!("Non-ascii header value: {}", SECRET_KEY);
+                    Error::from(HttpResponse::InternalServerError().body(msg))
+                })
+        ).map(|secret_key| {
+            Identity::SecretKey(hasher.hash(secret_key))
+        })
+}
+
 pub fn config<A: 'static + PollOperationsT>(cfg: &mut ServiceConfig) {
     cfg.route(paths::POST_POLL_PATH,
               web::post().to(paths::post_poll_handler::<A>))
+        .route(paths::LIST_POLLS_PATH,
+               web::get().to(paths::list_polls_handler::<A>))
         .route(paths::GET_POLL_PATH,
                web::get().to(paths::get_poll_handler::<A>))
+        .route(paths::PATCH_POLL_PATH,
+               web::patch().to(paths::patch_poll_handler::<A>))
+        .route(paths::DELETE_POLL_PATH,
+               web::delete().to(paths::delete_poll_handler::<A>))
         .route(paths::PUT_BALLOT_PATH,
                web::put().to(paths::put_ballot_handler::<A>))
+        .route(paths::LIST_BALLOTS_PATH,
+               web::get().to(paths::list_ballots_handler::<A>))
+        .route(paths::GET_BALLOT_PATH,
+               web::get().to(paths::get_ballot_handler::<A>))
+        .route(paths::DELETE_BALLOT_PATH,
+               web::delete().to(paths::delete_ballot_handler::<A>))
         .route(paths::POST_CANDIDATE_PATH,
             web::post().to(paths::post_candidate_handler::<A>))
+        .route(paths::PATCH_CANDIDATE_PATH,
+            web::patch().to(paths::patch_candidate_handler::<A>))
+        .route(paths::GET_PENDING_CANDIDATES_PATH,
+            web::get().to(paths::get_pending_candidates_handler::<A>))
+        .route(paths::GET_WRITE_INS_PATH,
+            web::get().to(paths::get_write_ins_handler::<A>))
+        .route(paths::GET_ME_PATH,
+            web::get().to(paths::get_me_handler))
+        .route(paths::GET_MY_BALLOTS_PATH,
+            web::get().to(paths::get_my_ballots_handler::<A>))
+        .route(paths::ADMINS_PATH,
+            web::get().to(paths::get_admins_handler::<A>))
+        .route(paths::ADMINS_PATH,
+            web::post().to(paths::post_admin_handler::<A>))
+        .route(paths::DELETE_ADMIN_PATH,
+            web::delete().to(paths::delete_admin_handler::<A>))
+        .route(paths::INVITATIONS_PATH,
+            web::post().to(paths::post_invitations_handler::<A>))
+        .route(paths::INVITATIONS_PATH,
+            web::get().to(paths::get_invitations_handler::<A>))
+        .route(paths::REDEEM_INVITATION_PATH,
+            web::post().to(paths::redeem_invitation_handler::<A>))
+        .route(paths::APPROVE_CANDIDATE_PATH,
+            web::post().to(paths::approve_candidate_handler::<A>))
+        .route(paths::REJECT_CANDIDATE_PATH,
+            web::post().to(paths::reject_candidate_handler::<A>))
+        .route(paths::GET_RESULTS_PATH,
+            web::get().to(paths::get_results_handler::<A>))
+        .route(paths::CLOSE_POLL_PATH,
+            web::post().to(paths::close_poll_handler::<A>))
     ;
 }
 
@@ -67,10 +160,10 @@ mod tests {
         let request_body = PostPollRequest {
             name: "test name".to_string(),
             description: Some("test description".to_string()),
+            expires: None,
+            close: None,
             candidates: Vec::new(),
-            configuration: Configuration {
-                write_ins: false,
-            },
+            configuration: Configuration::default(),
         };
 
         let mock_poll_id = "mock poll id";
@@ -81,17 +174,20 @@ mod tests {
             expires: Utc::now(),
             close: None,
             candidates: vec!(),
-            configuration: Configuration {
-                write_ins: false
-            }
+            configuration: Configuration::default(),
+            seed: 0,
         }});
         
+        let hasher = SecretHasher::new(b"test key");
+        let expected_owner = hasher.hash("my_secret");
         mock_ops.expect_post_poll()
+            .withf(move |id, _| id.owner_id() == expected_owner)
             .return_once(move |_, _| mock_response);
 
         let mut app = test::init_service(
             App::new()
                 .data(mock_ops)
+                .data(hasher)
                 .configure(config::<MockPollOperationsT>)
         ).await;
 
@@ -106,4 +202,69 @@ mod tests {
         let response_body: PostPollResponse = test::read_body_json(response).await;
         assert_eq!(mock_poll_id, response_body.poll.id);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_get_results() {
+        let mut mock_ops = operations::MockPollOperationsT::new();
+
+        mock_ops.expect_get_results()
+            .withf(|poll_id, method| poll_id == "mock_poll_id" && *method == Some(TabulationMethod::Irv))
+            .return_once(|_, _| Ok(GetResultsResponse {
+                official: true,
+                results: Results::Irv(IrvResults {
+                    rounds: vec!(),
+                    winner: None,
+                }),
+                ties: vec!(),
+            }));
+
+        let mut app = test::init_service(
+            App::new()
+                .data(mock_ops)
+                .data(SecretHasher::new(b"test key"))
+                .configure(config::<MockPollOperationsT>)
+        ).await;
+
+        let request = test::TestRequest::get()
+            .uri("/polls/mock_poll_id/results?method=irv")
+            .to_request();
+        let response = test::call_service(&mut app, request).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token() {
+        let mut mock_ops = operations::MockPollOperationsT::new();
+
+        mock_ops.expect_close_poll()
+            .withf(|poll_id, id| poll_id == "mock_poll_id" && matches!(id, Identity::Token(_))
+                && id.owner_id() == "token:user 1")
+            .times(1)
+            .return_once(|_, _| Ok(()));
+
+        let mut app = test::init_service(
+            App::new()
+                .data(mock_ops)
+                .data(SecretHasher::new(b"test key"))
+                .data(TokenValidator::new(b"token key"))
+                .configure(config::<MockPollOperationsT>)
+        ).await;
+
+        let token = crate::token::tests::sign(b"token key", "user 1", chrono::Duration::minutes(5));
+        let request = test::TestRequest::with_header(AUTHORIZATION, format!("Bearer {}", token))
+            .uri("/polls/mock_poll_id/close")
+            .method(Method::POST)
+            .to_request();
+        let response = test::call_service(&mut app, request).await;
+        assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+        let forged = crate::token::tests::sign(b"wrong key", "user 1", chrono::Duration::minutes(5));
+        let request = test::TestRequest::with_header(AUTHORIZATION, format!("Bearer {}", forged))
+            .uri("/polls/mock_poll_id/close")
+            .method(Method::POST)
+            .to_request();
+        let response = test::call_service(&mut app, request).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    }
+}
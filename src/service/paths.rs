@@ -1,21 +1,61 @@
 use actix_web::Result;
-use actix_web::web::{Data, HttpResponse, Path, Json};
+use actix_web::web::{Data, HttpResponse, Path, Json, Query};
 
 use crate::{
     model::*,
-    operations::{GetPollError, PostCandidateError, PollOperationsT, PostPollError, PutBallotError}
+    operations::{
+        ClosePollError,
+        DeleteBallotError,
+        DeletePollError,
+        GetBallotError,
+        GetMyBallotsError,
+        GetPollError,
+        GetResultsError,
+        InvitationError,
+        ListBallotsError,
+        ListPollsError,
+        ModerateCandidateError,
+        PollAdminError,
+        PostCandidateError,
+        PollOperationsT,
+        PostPollError,
+        PutBallotError,
+        RedeemInvitationError,
+        UpdateCandidateError,
+        UpdatePollError,
+    }
 };
 
 pub const POST_POLL_PATH: &str = "/polls";
+pub const LIST_POLLS_PATH: &str = "/polls";
 pub const POST_CANDIDATE_PATH: &str = "/polls/{poll_id}/candidates";
+pub const PATCH_CANDIDATE_PATH: &str = "/polls/{poll_id}/candidates/{candidate}";
+pub const GET_PENDING_CANDIDATES_PATH: &str = "/polls/{poll_id}/pending-candidates";
+pub const GET_WRITE_INS_PATH: &str = "/polls/{poll_id}/write-ins";
+pub const APPROVE_CANDIDATE_PATH: &str = "/polls/{poll_id}/pending-candidates/{candidate}/approve";
+pub const REJECT_CANDIDATE_PATH: &str = "/polls/{poll_id}/pending-candidates/{candidate}/reject";
+pub const INVITATIONS_PATH: &str = "/polls/{poll_id}/invitations";
+pub const REDEEM_INVITATION_PATH: &str = "/polls/{poll_id}/invitations/{token}/redeem";
+pub const ADMINS_PATH: &str = "/polls/{poll_id}/admins";
+pub const DELETE_ADMIN_PATH: &str = "/polls/{poll_id}/admins/{admin_id}";
+pub const GET_ME_PATH: &str = "/me";
+pub const GET_MY_BALLOTS_PATH: &str = "/me/ballots";
 pub const GET_POLL_PATH: &str = "/polls/{poll_id}";
+pub const PATCH_POLL_PATH: &str = "/polls/{poll_id}";
+pub const DELETE_POLL_PATH: &str = "/polls/{poll_id}";
 pub const PUT_BALLOT_PATH: &str = "/polls/{poll_id}/ballots/{ballot_id}";
+pub const LIST_BALLOTS_PATH: &str = "/polls/{poll_id}/ballots";
+pub const GET_BALLOT_PATH: &str = "/polls/{poll_id}/ballots/{ballot_id}";
+pub const DELETE_BALLOT_PATH: &str = "/polls/{poll_id}/ballots/{ballot_id}";
+pub const GET_RESULTS_PATH: &str = "/polls/{poll_id}/results";
+pub const CLOSE_POLL_PATH: &str = "/polls/{poll_id}/close";
 
 pub async fn get_poll_handler<A: 'static + PollOperationsT> (
     ops: Data<A>,
-    path: Path<String>) -> Result<Json<GetPollResponse>>
+    path: Path<String>,
+    query: Query<GetPollQuery>) -> Result<Json<GetPollResponse>>
 {
-    let poll = ops.get_poll(&path)
+    let poll = ops.get_poll(&path, !query.omit_ballots)
         .await
         .map_err(|e| match e {
             GetPollError::NotFound =>
@@ -26,24 +66,329 @@ pub async fn get_poll_handler<A: 'static + PollOperationsT> (
     Ok(Json(poll))
 }
 
+pub async fn list_polls_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    query: Query<ListPollsQuery>,
+    id: Identity,
+) -> Result<Json<ListPollsResponse>> {
+    let Query(query) = query;
+    if query.owner != "me" {
+        return Err(HttpResponse::BadRequest().body("Only owner=me is supported.").into());
+    }
+    let polls = ops.list_polls(&id, query.cursor, query.limit)
+        .await
+        .map_err(|e| match e {
+            ListPollsError::InvalidCursor => HttpResponse::BadRequest().body("Invalid cursor."),
+            ListPollsError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(Json(polls))
+}
+
+pub async fn get_results_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    query: Query<GetResultsQuery>,
+) -> Result<Json<GetResultsResponse>> {
+    let results = ops.get_results(&poll_id, query.method)
+        .await
+        .map_err(|e| match e {
+            GetResultsError::NotFound => HttpResponse::NotFound().finish(),
+            GetResultsError::InvalidMethod(message) => HttpResponse::BadRequest().body(message),
+            GetResultsError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(Json(results))
+}
+
+pub async fn patch_poll_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    body: Json<PatchPollRequest>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    let Json(request_body) = body;
+    ops.update_poll(&poll_id, &id, &request_body)
+        .await
+        .map_err(|e| match e {
+            UpdatePollError::PollNotFound => HttpResponse::NotFound().finish(),
+            UpdatePollError::NotOwner => HttpResponse::Forbidden().finish(),
+            UpdatePollError::PollClosed => HttpResponse::Conflict().body("Poll is closed."),
+            UpdatePollError::LockedByBallots(field) =>
+                HttpResponse::Conflict().body(format!("[{}] cannot change once ballots have been cast.", field)),
+            UpdatePollError::InvalidSchedule(message) => HttpResponse::BadRequest().body(message),
+            UpdatePollError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn delete_poll_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    ops.delete_poll(&poll_id, &id)
+        .await
+        .map_err(|e| match e {
+            DeletePollError::PollNotFound => HttpResponse::NotFound().finish(),
+            DeletePollError::NotOwner => HttpResponse::Forbidden().finish(),
+            DeletePollError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn close_poll_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    ops.close_poll(&poll_id, &id)
+        .await
+        .map_err(|e| match e {
+            ClosePollError::PollNotFound => HttpResponse::NotFound().finish(),
+            ClosePollError::NotOwner => HttpResponse::Forbidden().finish(),
+            ClosePollError::AlreadyClosed => HttpResponse::Conflict().body("Poll is already closed."),
+            ClosePollError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub async fn post_candidate_handler<A: 'static + PollOperationsT>(
     ops: Data<A>,
     Path(poll_id): Path<String>,
     body: Json<Candidate>,
+    id: Identity,
 ) -> Result<HttpResponse> {
     let Json(candidate) = body;
-    ops.post_candidate(&poll_id, &candidate)
+    ops.post_candidate(&poll_id, &id, &candidate)
     .await
     .map_err(|e| match e {
         PostCandidateError::PollNotFound => HttpResponse::NotFound().finish(),
+        PostCandidateError::PollClosed => HttpResponse::Conflict().body("Poll is closed."),
         PostCandidateError::NoWriteIns => HttpResponse::BadRequest().body("Write-ins not allowed for this poll."),
-        PostCandidateError::DuplicateCandidate(_) => HttpResponse::Conflict().finish(),
+        PostCandidateError::OidcRequired => HttpResponse::Forbidden().body("Sign in through OIDC to take part in this poll."),
+        PostCandidateError::WriteInLimitReached =>
+            HttpResponse::Forbidden().body("Write-in limit reached for this poll."),
+        PostCandidateError::DuplicateCandidate(dupe_name) =>
+            HttpResponse::Conflict().body(format!("Duplicate candidate name: [{}]", dupe_name)),
+        PostCandidateError::NameUnavailable =>
+            HttpResponse::Conflict().body("Candidate name is unavailable."),
         PostCandidateError::Unexpected => HttpResponse::InternalServerError().finish(),
     })?;
 
     Ok(HttpResponse::NoContent().finish())
 }
 
+pub async fn patch_candidate_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path((poll_id, candidate)): Path<(String, String)>,
+    body: Json<PatchCandidateRequest>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    let Json(request_body) = body;
+    ops.update_candidate(&poll_id, &id, &candidate, &request_body)
+    .await
+    .map_err(|e| match e {
+        UpdateCandidateError::PollNotFound => HttpResponse::NotFound().finish(),
+        UpdateCandidateError::CandidateNotFound => HttpResponse::NotFound().finish(),
+        UpdateCandidateError::NotOwner => HttpResponse::Forbidden().finish(),
+        UpdateCandidateError::PollClosed => HttpResponse::Conflict().body("Poll is closed."),
+        UpdateCandidateError::DuplicateCandidate(dupe_name) =>
+            HttpResponse::Conflict().body(format!("Duplicate candidate name: [{}]", dupe_name)),
+        UpdateCandidateError::LockedByBallots =>
+            HttpResponse::Conflict().body("Candidate name cannot change once ballots rank it."),
+        UpdateCandidateError::Unexpected => HttpResponse::InternalServerError().finish(),
+    })?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn moderate_candidate_error(e: ModerateCandidateError) -> HttpResponse {
+    match e {
+        ModerateCandidateError::PollNotFound => HttpResponse::NotFound().finish(),
+        ModerateCandidateError::PollClosed => HttpResponse::Conflict().body("Poll is closed."),
+        ModerateCandidateError::CandidateNotFound => HttpResponse::NotFound().finish(),
+        ModerateCandidateError::NotOwner => HttpResponse::Forbidden().finish(),
+        ModerateCandidateError::Unexpected => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+pub async fn get_pending_candidates_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    id: Identity,
+) -> Result<Json<GetCandidatesResponse>> {
+    let candidates = ops.get_pending_candidates(&poll_id, &id)
+        .await
+        .map_err(moderate_candidate_error)?;
+    Ok(Json(candidates))
+}
+
+pub async fn get_write_ins_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    id: Identity,
+) -> Result<Json<GetWriteInsResponse>> {
+    let write_ins = ops.get_write_ins(&poll_id, &id)
+        .await
+        .map_err(moderate_candidate_error)?;
+    Ok(Json(write_ins))
+}
+
+/// The caller's id as stored on their polls and ballots, which they can share with a
+/// poll owner to be made an admin.
+pub async fn get_me_handler(id: Identity) -> Json<GetMeResponse> {
+    Json(GetMeResponse {
+        id: id.owner_id().to_string(),
+    })
+}
+
+pub async fn get_my_ballots_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    id: Identity,
+) -> Result<Json<GetMyBallotsResponse>> {
+    let ballots = ops.get_my_ballots(&id)
+        .await
+        .map_err(|e| match e {
+            GetMyBallotsError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(Json(ballots))
+}
+
+pub async fn list_ballots_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    query: Query<ListBallotsQuery>,
+) -> Result<Json<ListBallotsResponse>> {
+    let ballots = ops.list_ballots(&poll_id, &query)
+        .await
+        .map_err(|e| match e {
+            ListBallotsError::PollNotFound => HttpResponse::NotFound().finish(),
+            ListBallotsError::BallotsHidden => HttpResponse::Forbidden().body("Ballots are hidden for this poll."),
+            ListBallotsError::InvalidCursor => HttpResponse::BadRequest().body("Invalid cursor."),
+            ListBallotsError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(Json(ballots))
+}
+
+fn poll_admin_error(e: PollAdminError) -> HttpResponse {
+    match e {
+        PollAdminError::PollNotFound | PollAdminError::AdminNotFound => HttpResponse::NotFound().finish(),
+        PollAdminError::NotOwner => HttpResponse::Forbidden().finish(),
+        PollAdminError::Unexpected => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+pub async fn get_admins_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    id: Identity,
+) -> Result<Json<GetAdminsResponse>> {
+    let admins = ops.get_admins(&poll_id, &id)
+        .await
+        .map_err(poll_admin_error)?;
+    Ok(Json(admins))
+}
+
+pub async fn post_admin_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    body: Json<PostAdminRequest>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    ops.add_admin(&poll_id, &id, &body.id)
+        .await
+        .map_err(poll_admin_error)?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn delete_admin_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path((poll_id, admin_id)): Path<(String, String)>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    ops.remove_admin(&poll_id, &id, &admin_id)
+        .await
+        .map_err(poll_admin_error)?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn invitation_error(e: InvitationError) -> HttpResponse {
+    match e {
+        InvitationError::PollNotFound => HttpResponse::NotFound().finish(),
+        InvitationError::NotOwner => HttpResponse::Forbidden().finish(),
+        InvitationError::NotPrivate => HttpResponse::BadRequest().body("Poll is not private."),
+        InvitationError::DuplicateInvitee(invitee) => {
+            let message = format!("Duplicate invitee: [{}]", invitee);
+            HttpResponse::Conflict().body(message)
+        },
+        InvitationError::Unexpected => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+pub async fn post_invitations_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    body: Json<PostInvitationsRequest>,
+    id: Identity,
+) -> Result<Json<GetInvitationsResponse>> {
+    let Json(request) = body;
+    let invitations = ops.post_invitations(&poll_id, &id, &request)
+        .await
+        .map_err(invitation_error)?;
+    Ok(Json(invitations))
+}
+
+pub async fn get_invitations_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path(poll_id): Path<String>,
+    id: Identity,
+) -> Result<Json<GetInvitationsResponse>> {
+    let invitations = ops.get_invitations(&poll_id, &id)
+        .await
+        .map_err(invitation_error)?;
+    Ok(Json(invitations))
+}
+
+pub async fn redeem_invitation_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path((poll_id, token)): Path<(String, String)>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    ops.redeem_invitation(&poll_id, &id, &token)
+        .await
+        .map_err(|e| match e {
+            RedeemInvitationError::PollNotFound | RedeemInvitationError::InvitationNotFound =>
+                HttpResponse::NotFound().finish(),
+            RedeemInvitationError::AlreadyRedeemed =>
+                HttpResponse::Conflict().body("Invitation has already been used."),
+            RedeemInvitationError::AlreadyInvited =>
+                HttpResponse::Conflict().body("Already holding an invitation to this poll."),
+            RedeemInvitationError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn approve_candidate_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path((poll_id, candidate)): Path<(String, String)>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    ops.moderate_candidate(&poll_id, &id, &candidate, true)
+        .await
+        .map_err(moderate_candidate_error)?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn reject_candidate_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path((poll_id, candidate)): Path<(String, String)>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    ops.moderate_candidate(&poll_id, &id, &candidate, false)
+        .await
+        .map_err(moderate_candidate_error)?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub async fn post_poll_handler<A: 'static + PollOperationsT>(
     ops: Data<A>,
     body: Json<PostPollRequest>,
@@ -57,7 +402,11 @@ pub async fn post_poll_handler<A: 'static + PollOperationsT>(
             PostPollError::DuplicateCandidate(dupe_name) =>
                 HttpResponse::BadRequest().body(
                     format!("Duplicate candidate name: [{}]", dupe_name)
-                )
+                ),
+            PostPollError::InvalidConfiguration(message) =>
+                HttpResponse::BadRequest().body(message),
+            PostPollError::InvalidSchedule(message) =>
+                HttpResponse::BadRequest().body(message),
         })?;
     Ok(Json(ok))
 }
@@ -72,9 +421,13 @@ pub async fn put_ballot_handler<A: 'static + PollOperationsT>(
             .await
             .map_err(|e| match e {
                 PutBallotError::PollNotFound => HttpResponse::NotFound().finish(),
+                PutBallotError::PollClosed => HttpResponse::Conflict().body("Poll is closed."),
                 PutBallotError::Unexpected => HttpResponse::InternalServerError().finish(),
                 PutBallotError::NotOwner => HttpResponse::Forbidden().finish(),
                 PutBallotError::NotSameName => HttpResponse::BadRequest().finish(),
+                PutBallotError::NotInvited => HttpResponse::Forbidden().body("Not invited to this poll."),
+                PutBallotError::OidcRequired => HttpResponse::Forbidden().body("Sign in through OIDC to take part in this poll."),
+                PutBallotError::AlreadyVoted => HttpResponse::Conflict().body("Already voted in this poll."),
                 PutBallotError::DuplicateRanking(candidate) => {
                     let message = format!("Duplicate ranking: [{}]", candidate);
                     HttpResponse::BadRequest().body(message)
@@ -83,6 +436,41 @@ pub async fn put_ballot_handler<A: 'static + PollOperationsT>(
                     let message = format!("Invalid candidate: [{}]", name);
                     HttpResponse::BadRequest().body(message)
                 }
+                PutBallotError::CandidateWithdrawn(name) => {
+                    let message = format!("Withdrawn candidate: [{}]", name);
+                    HttpResponse::BadRequest().body(message)
+                }
+            })?;
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+pub async fn get_ballot_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path((poll_id, ballot_id)): Path<(String, String)>,
+    user_id: Option<Identity>,
+) -> Result<Json<BallotSummary>> {
+    let ballot = ops.get_ballot(&poll_id, &user_id, &ballot_id)
+        .await
+        .map_err(|e| match e {
+            GetBallotError::PollNotFound | GetBallotError::BallotNotFound => HttpResponse::NotFound().finish(),
+            GetBallotError::NotOwner => HttpResponse::Forbidden().finish(),
+            GetBallotError::Unexpected => HttpResponse::InternalServerError().finish(),
+        })?;
+    Ok(Json(ballot))
+}
+
+pub async fn delete_ballot_handler<A: 'static + PollOperationsT>(
+    ops: Data<A>,
+    Path((poll_id, ballot_id)): Path<(String, String)>,
+    user_id: Identity) -> Result<HttpResponse> {
+        ops.delete_ballot(&poll_id, &user_id, &ballot_id)
+            .await
+            .map_err(|e| match e {
+                DeleteBallotError::PollNotFound => HttpResponse::NotFound().finish(),
+                DeleteBallotError::BallotNotFound => HttpResponse::NotFound().finish(),
+                DeleteBallotError::PollClosed => HttpResponse::Conflict().body("Poll is closed."),
+                DeleteBallotError::NotOwner => HttpResponse::Forbidden().finish(),
+                DeleteBallotError::Unexpected => HttpResponse::InternalServerError().finish(),
             })?;
         Ok(HttpResponse::NoContent().finish())
     }
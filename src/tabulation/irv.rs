@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+
+use crate::model::{IrvResults, IrvRound, Tally};
+
+use super::{Election, TieBreaker};
+
+/// Counts the election by instant-runoff voting.
+///
+/// Each round, every ballot counts toward its highest ranked candidate still in the
+/// running. A candidate holding a majority of the non-exhausted ballots wins; otherwise
+/// the candidate with the fewest votes is eliminated and the count is repeated.
+/// Ties for last place are resolved by `tie_breaker`.
+pub fn irv(election: &Election, tie_breaker: &mut TieBreaker) -> IrvResults {
+    let mut continuing: HashSet<usize> = (0..election.candidates.len()).collect();
+    let mut rounds = Vec::new();
+    let mut history: Vec<Vec<f64>> = Vec::new();
+
+    loop {
+        let mut votes = vec![0u32; election.candidates.len()];
+        let mut exhausted = 0;
+        for ballot in election.ballots.iter() {
+            match ballot.iter().find(|c| continuing.contains(c)) {
+                Some(&c) => votes[c] += 1,
+                None => exhausted += 1,
+            }
+        }
+
+        let mut standing: Vec<usize> = continuing.iter().copied().collect();
+        standing.sort_by_key(|&c| (std::cmp::Reverse(votes[c]), c));
+
+        let tallies = standing.iter()
+            .map(|&c| Tally {
+                candidate: election.name(c),
+                votes: votes[c],
+            })
+            .collect();
+
+        let active = election.ballots.len() as u32 - exhausted;
+        let leader = standing.first().copied();
+        let winner = leader.filter(|&c| standing.len() == 1 || votes[c] * 2 > active);
+
+        if active == 0 || winner.is_some() {
+            rounds.push(IrvRound {
+                tallies,
+                exhausted,
+                eliminated: None,
+            });
+            return IrvResults {
+                rounds,
+                winner: winner.filter(|_| active > 0).map(|c| election.name(c)),
+            };
+        }
+
+        let fewest = votes[*standing.last().expect("at least two candidates remain")];
+        let tied: Vec<usize> = standing.iter()
+            .copied()
+            .filter(|&c| votes[c] == fewest)
+            .collect();
+        let eliminated = if tied.len() > 1 {
+            *tie_breaker.resolve(election, &tied, &history, Some(rounds.len()))
+                .last()
+                .expect("tie has candidates")
+        } else {
+            tied[0]
+        };
+        continuing.remove(&eliminated);
+        history.push(votes.iter().map(|&v| v as f64).collect());
+
+        rounds.push(IrvRound {
+            tallies,
+            exhausted,
+            eliminated: Some(election.name(eliminated)),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::TieBreak;
+    use crate::tabulation::election;
+
+    use super::*;
+
+    #[test]
+    fn majority_in_first_round() {
+        let e = election(&["🍦", "🍪", "🎂"], vec!((1, vec!(0, 1)), (1, vec!(0)), (1, vec!(1, 0))));
+        let results = irv(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(1, results.rounds.len());
+        assert_eq!(Some("🍦"), results.winner.as_deref().map(|w| w.as_str()));
+    }
+
+    #[test]
+    fn transfers_after_elimination() {
+        // 🎂 is eliminated first and its ballot transfers to 🍪
+        let e = election(&["🍦", "🍪", "🎂"], vec!(
+            (2, vec!(0)),
+            (2, vec!(1)),
+            (1, vec!(2, 1)),
+        ));
+        let results = irv(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(2, results.rounds.len());
+        assert_eq!(Some("🎂"), results.rounds[0].eliminated.as_deref().map(|c| c.as_str()));
+        assert_eq!(Some("🍪"), results.winner.as_deref().map(|w| w.as_str()));
+    }
+
+    #[test]
+    fn counts_exhausted_ballots() {
+        let e = election(&["🍦", "🍪", "🎂"], vec!(
+            (3, vec!(0)),
+            (2, vec!(1)),
+            (1, vec!(2)),
+        ));
+        let results = irv(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        let last = results.rounds.last().expect("should have rounds");
+        assert_eq!(1, last.exhausted);
+        assert_eq!(Some("🍦"), results.winner.as_deref().map(|w| w.as_str()));
+    }
+
+    #[test]
+    fn breaks_elimination_tie() {
+        let e = election(&["🍦", "🍪", "🎂"], vec!((2, vec!(0)), (1, vec!(1)), (1, vec!(2))));
+        let mut tie_breaker = TieBreaker::new(&e, TieBreak::Lexicographic, 0, &[]);
+        let results = irv(&e, &mut tie_breaker);
+
+        // 🍪 sorts before 🎂, so 🎂 is eliminated
+        assert_eq!(Some("🎂"), results.rounds[0].eliminated.as_deref().map(|c| c.as_str()));
+        assert_eq!(1, tie_breaker.into_ties().len());
+    }
+
+    #[test]
+    fn no_ballots_no_winner() {
+        let e = election(&["🍦", "🍪"], vec!());
+        let results = irv(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(1, results.rounds.len());
+        assert!(results.winner.is_none());
+    }
+}
@@ -0,0 +1,95 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::model::GetPollResponse;
+
+mod irv;
+mod ranked_pairs;
+mod schulze;
+mod scoring;
+mod stv;
+mod tie;
+
+pub use irv::irv;
+pub use ranked_pairs::ranked_pairs;
+pub use schulze::schulze;
+pub use scoring::{scoring, Positional};
+pub use stv::stv;
+pub use tie::TieBreaker;
+
+/// A poll's ballots in a form suited to counting: candidates are referred to by
+/// their index in `candidates`, and each ballot lists them from most to least preferred.
+pub struct Election {
+    pub candidates: Vec<Arc<String>>,
+    pub ballots: Vec<Vec<usize>>,
+}
+
+impl Election {
+    pub fn new(candidates: Vec<Arc<String>>, ballots: Vec<Vec<usize>>) -> Election {
+        Election {
+            candidates,
+            ballots,
+        }
+    }
+
+    fn name(&self, candidate: usize) -> Arc<String> {
+        self.candidates[candidate].clone()
+    }
+
+    fn names(&self, candidates: &[usize]) -> Vec<Arc<String>> {
+        candidates.iter().map(|&c| self.name(c)).collect()
+    }
+
+    /// Number of ballots preferring each candidate over each other candidate, indexed
+    /// `[preferred][other]`. A ranked candidate is preferred over every unranked one.
+    fn pairwise(&self) -> Vec<Vec<u32>> {
+        let n = self.candidates.len();
+        let mut matrix = vec![vec![0u32; n]; n];
+        for ballot in self.ballots.iter() {
+            let mut below: Vec<bool> = vec![true; n];
+            for &preferred in ballot.iter() {
+                below[preferred] = false;
+                for (other, &is_below) in below.iter().enumerate() {
+                    if is_below {
+                        matrix[preferred][other] += 1;
+                    }
+                }
+            }
+        }
+        matrix
+    }
+}
+
+/// Builds an election for tests from candidate names and `(count, ballot)` pairs,
+/// each ballot being repeated `count` times.
+#[cfg(test)]
+fn election(candidates: &[&str], ballots: Vec<(usize, Vec<usize>)>) -> Election {
+    Election::new(
+        candidates.iter().map(|c| Arc::new(c.to_string())).collect(),
+        ballots.into_iter()
+            .flat_map(|(count, ballot)| itertools::repeat_n(ballot, count))
+            .collect(),
+    )
+}
+
+/// Withdrawn candidates are left out, so ballots ranking them count toward their next choice.
+impl From<&GetPollResponse> for Election {
+    fn from(poll: &GetPollResponse) -> Self {
+        let candidates: Vec<Arc<String>> = poll.poll.candidates.iter()
+            .filter(|c| !c.withdrawn)
+            .map(|c| Arc::new(c.name.clone()))
+            .collect();
+
+        let name_to_index: HashMap<&str, usize> = candidates.iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+
+        let ballots = poll.ballots.iter()
+            .map(|b| b.rankings.iter()
+                .flat_map(|r| name_to_index.get(r.as_str()).copied())
+                .collect())
+            .collect();
+
+        Election::new(candidates, ballots)
+    }
+}
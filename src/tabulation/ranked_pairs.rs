@@ -0,0 +1,121 @@
+use std::cmp::Reverse;
+
+use crate::model::{RankedPair, RankedPairsResults};
+
+use super::{Election, TieBreaker};
+
+/// Counts the election by Tideman's ranked pairs.
+///
+/// Pairwise victories are sorted by the number of ballots in their favour (then by
+/// the smallest opposition, then by `tie_breaker`'s priority of the winner and loser)
+/// and locked in that order, skipping any victory that would complete a cycle with
+/// those already locked. The order is read from the locked graph: candidates no
+/// remaining candidate is locked over are placed together, and a tie for first place
+/// is resolved by `tie_breaker` to pick the winner.
+pub fn ranked_pairs(election: &Election, tie_breaker: &mut TieBreaker) -> RankedPairsResults {
+    let n = election.candidates.len();
+    let pairwise = election.pairwise();
+
+    let mut victories: Vec<(usize, usize)> = (0..n)
+        .flat_map(|w| (0..n).map(move |l| (w, l)))
+        .filter(|&(w, l)| pairwise[w][l] > pairwise[l][w])
+        .collect();
+    victories.sort_by_key(|&(w, l)| (
+        Reverse(pairwise[w][l]),
+        pairwise[l][w],
+        tie_breaker.priority(w),
+        Reverse(tie_breaker.priority(l)),
+    ));
+
+    let mut locked = vec![vec![false; n]; n];
+    let pairs = victories.into_iter()
+        .map(|(w, l)| {
+            let lock = !reaches(&locked, l, w);
+            if lock {
+                locked[w][l] = true;
+            }
+            RankedPair {
+                winner: election.name(w),
+                loser: election.name(l),
+                votes: pairwise[w][l],
+                opposition: pairwise[l][w],
+                locked: lock,
+            }
+        })
+        .collect();
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut tiers = Vec::new();
+    while !remaining.is_empty() {
+        let (top, rest): (Vec<usize>, Vec<usize>) = remaining.iter()
+            .partition(|&&c| remaining.iter().all(|&o| !locked[o][c]));
+        tiers.push(top);
+        remaining = rest;
+    }
+
+    let winner = tiers.first().map(|top| match top.len() {
+        1 => top[0],
+        _ => tie_breaker.resolve(election, top, &[], None)[0],
+    });
+
+    RankedPairsResults {
+        pairs,
+        order: tiers.iter().map(|tier| election.names(tier)).collect(),
+        winner: winner.map(|c| election.name(c)),
+    }
+}
+
+/// Whether a path of locked victories leads from `from` to `to`.
+fn reaches(locked: &[Vec<bool>], from: usize, to: usize) -> bool {
+    let mut visited = vec![false; locked.len()];
+    let mut stack = vec![from];
+    while let Some(c) = stack.pop() {
+        if c == to {
+            return true;
+        }
+        if !visited[c] {
+            visited[c] = true;
+            stack.extend((0..locked.len()).filter(|&next| locked[c][next]));
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::TieBreak;
+    use crate::tabulation::election;
+
+    use super::*;
+
+    #[test]
+    fn skips_pair_completing_cycle() {
+        // 🍦 > 🍪 (7-2), 🍪 > 🎂 (6-3), 🎂 > 🍦 (5-4)
+        let e = election(&["🍦", "🍪", "🎂"], vec!(
+            (4, vec!(0, 1, 2)),
+            (3, vec!(2, 0, 1)),
+            (2, vec!(1, 2, 0)),
+        ));
+        let results = ranked_pairs(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        let pairs: Vec<(&str, &str, bool)> = results.pairs.iter()
+            .map(|p| (p.winner.as_str(), p.loser.as_str(), p.locked))
+            .collect();
+        assert_eq!(vec!(("🍦", "🍪", true), ("🍪", "🎂", true), ("🎂", "🍦", false)), pairs);
+
+        let order: Vec<Vec<&str>> = results.order.iter()
+            .map(|tier| tier.iter().map(|c| c.as_str()).collect())
+            .collect();
+        assert_eq!(vec!(vec!("🍦"), vec!("🍪"), vec!("🎂")), order);
+    }
+
+    #[test]
+    fn pairwise_ties_are_not_victories() {
+        let e = election(&["🍦", "🍪"], vec!((1, vec!(0, 1)), (1, vec!(1, 0))));
+        let results = ranked_pairs(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert!(results.pairs.is_empty());
+        assert_eq!(1, results.order.len());
+        assert!(results.winner.is_some());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::model::SchulzeResults;
+
+use super::{Election, TieBreaker};
+
+/// Counts the election by the Schulze method.
+///
+/// The strength of a path between two candidates is its weakest pairwise victory, and
+/// a candidate ranks above another when its strongest path to them is stronger than
+/// theirs in return. Candidates that no remaining candidate ranks above are placed
+/// together, so ties appear as a group in the order; a tie for first place is
+/// resolved by `tie_breaker` to pick the winner.
+pub fn schulze(election: &Election, tie_breaker: &mut TieBreaker) -> SchulzeResults {
+    let n = election.candidates.len();
+    let pairwise = election.pairwise();
+
+    let mut paths = vec![vec![0u32; n]; n];
+    for i in 0..n {
+        for j in 0..n {
+            if i != j && pairwise[i][j] > pairwise[j][i] {
+                paths[i][j] = pairwise[i][j];
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            if i == k {
+                continue;
+            }
+            for j in 0..n {
+                if j != i && j != k {
+                    paths[i][j] = paths[i][j].max(paths[i][k].min(paths[k][j]));
+                }
+            }
+        }
+    }
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut tiers = Vec::new();
+    while !remaining.is_empty() {
+        let (top, rest): (Vec<usize>, Vec<usize>) = remaining.iter()
+            .partition(|&&c| remaining.iter().all(|&o| paths[o][c] <= paths[c][o]));
+        tiers.push(top);
+        remaining = rest;
+    }
+
+    let winner = tiers.first().map(|top| match top.len() {
+        1 => top[0],
+        _ => tie_breaker.resolve(election, top, &[], None)[0],
+    });
+
+    SchulzeResults {
+        candidates: election.candidates.clone(),
+        pairwise,
+        strongest_paths: paths,
+        order: tiers.iter().map(|tier| election.names(tier)).collect(),
+        winner: winner.map(|c| election.name(c)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::TieBreak;
+    use crate::tabulation::election;
+
+    use super::*;
+
+    fn order(results: &SchulzeResults) -> Vec<Vec<&str>> {
+        results.order.iter()
+            .map(|tier| tier.iter().map(|c| c.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn wikipedia_example() {
+        let e = election(&["A", "B", "C", "D", "E"], vec!(
+            (5, vec!(0, 2, 1, 4, 3)),
+            (5, vec!(0, 3, 4, 2, 1)),
+            (8, vec!(1, 4, 3, 0, 2)),
+            (3, vec!(2, 0, 1, 4, 3)),
+            (7, vec!(2, 0, 4, 1, 3)),
+            (2, vec!(2, 1, 0, 3, 4)),
+            (7, vec!(3, 2, 4, 1, 0)),
+            (8, vec!(4, 1, 0, 3, 2)),
+        ));
+        let results = schulze(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(20, results.pairwise[0][1]);
+        assert_eq!(28, results.strongest_paths[0][1]);
+        assert_eq!(25, results.strongest_paths[4][0]);
+        assert_eq!(vec!(vec!("E"), vec!("A"), vec!("C"), vec!("B"), vec!("D")), order(&results));
+    }
+
+    #[test]
+    fn unranked_candidates_lose_to_ranked() {
+        let e = election(&["🍦", "🍪", "🎂"], vec!((1, vec!(1))));
+        let results = schulze(&e, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(1, results.pairwise[1][0]);
+        assert_eq!(0, results.pairwise[0][2]);
+        assert_eq!(vec!(vec!("🍪"), vec!("🍦", "🎂")), order(&results));
+        assert_eq!(Some("🍪"), results.winner.as_deref().map(|w| w.as_str()));
+    }
+}
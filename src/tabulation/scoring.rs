@@ -0,0 +1,153 @@
+use std::cmp::Ordering;
+
+use crate::model::{Score, ScoringResults, UnrankedScoring};
+
+use super::{Election, TieBreaker};
+
+/// Points awarded for each position on a ballot.
+pub enum Positional<'a> {
+    /// `n - 1` points for first place down to none for last, for `n` candidates.
+    Borda,
+    /// `1 / position` points, starting at 1 for first place.
+    Dowdall,
+    /// Points for each position from first place; positions past the end score none.
+    Custom(&'a [u16]),
+}
+
+impl Positional<'_> {
+    fn points(&self, position: usize, field_size: usize) -> f64 {
+        match self {
+            Positional::Borda => field_size.saturating_sub(position + 1) as f64,
+            Positional::Dowdall => 1.0 / (position + 1) as f64,
+            Positional::Custom(points) => points.get(position).copied().unwrap_or(0) as f64,
+        }
+    }
+}
+
+/// Scores the election by awarding each candidate points for its position on every ballot.
+///
+/// Candidates a ballot leaves unranked are scored according to `unranked`:
+/// `Zero` gives them nothing, `Average` splits the points of the positions left
+/// unfilled evenly between them, and `Excluded` leaves them out of the ballot entirely,
+/// scoring it as though only the ranked candidates had been standing. A tie for the
+/// highest score is resolved by `tie_breaker` to pick the winner.
+pub fn scoring(election: &Election,
+    positional: Positional,
+    unranked: UnrankedScoring,
+    tie_breaker: &mut TieBreaker,
+) -> ScoringResults {
+    let n = election.candidates.len();
+    let mut points = vec![0f64; n];
+
+    for ballot in election.ballots.iter() {
+        let field_size = match unranked {
+            UnrankedScoring::Excluded => ballot.len(),
+            UnrankedScoring::Zero | UnrankedScoring::Average => n,
+        };
+        for (position, &c) in ballot.iter().enumerate() {
+            points[c] += positional.points(position, field_size);
+        }
+
+        let unranked_count = n - ballot.len();
+        if unranked == UnrankedScoring::Average && unranked_count > 0 {
+            let remaining: f64 = (ballot.len()..n)
+                .map(|position| positional.points(position, n))
+                .sum();
+            let share = remaining / unranked_count as f64;
+            for (c, p) in points.iter_mut().enumerate() {
+                if !ballot.contains(&c) {
+                    *p += share;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| points[b].partial_cmp(&points[a]).unwrap_or(Ordering::Equal).then(a.cmp(&b)));
+
+    let top = order.first().map(|&c| points[c]);
+    let winners: Vec<usize> = order.iter()
+        .copied()
+        .filter(|&c| Some(points[c]) == top)
+        .collect();
+    let winner = match winners.len() {
+        0 => None,
+        1 => Some(winners[0]),
+        _ => Some(tie_breaker.resolve(election, &winners, &[], None)[0]),
+    };
+
+    ScoringResults {
+        scores: order.iter()
+            .map(|&c| Score {
+                candidate: election.name(c),
+                points: points[c],
+            })
+            .collect(),
+        winners: election.names(&winners),
+        winner: winner.map(|c| election.name(c)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::model::TieBreak;
+    use crate::tabulation::election;
+
+    use super::*;
+
+    const CANDIDATES: [&str; 3] = ["🍦", "🍪", "🎂"];
+
+    fn score(e: &Election, positional: Positional, unranked: UnrankedScoring) -> ScoringResults {
+        scoring(e, positional, unranked, &mut TieBreaker::new(e, TieBreak::Random, 0, &[]))
+    }
+
+    fn points(results: &ScoringResults, candidate: &str) -> f64 {
+        results.scores.iter()
+            .find(|s| s.candidate.as_str() == candidate)
+            .expect("candidate should be scored")
+            .points
+    }
+
+    #[test]
+    fn borda_full_ballots() {
+        let e = election(&CANDIDATES, vec!((1, vec!(0, 1, 2)), (1, vec!(1, 0, 2)), (1, vec!(1, 2, 0))));
+        let results = score(&e, Positional::Borda, UnrankedScoring::Zero);
+
+        assert_eq!(3.0, points(&results, "🍦"));
+        assert_eq!(5.0, points(&results, "🍪"));
+        assert_eq!(1.0, points(&results, "🎂"));
+        assert_eq!(vec!(Arc::new("🍪".to_string())), results.winners);
+    }
+
+    #[test]
+    fn unranked_treatments() {
+        let e = election(&CANDIDATES, vec!((1, vec!(0))));
+
+        let zero = score(&e, Positional::Borda, UnrankedScoring::Zero);
+        assert_eq!(2.0, points(&zero, "🍦"));
+        assert_eq!(0.0, points(&zero, "🍪"));
+
+        let average = score(&e, Positional::Borda, UnrankedScoring::Average);
+        assert_eq!(2.0, points(&average, "🍦"));
+        assert_eq!(0.5, points(&average, "🍪"));
+        assert_eq!(0.5, points(&average, "🎂"));
+
+        let excluded = score(&e, Positional::Borda, UnrankedScoring::Excluded);
+        assert_eq!(0.0, points(&excluded, "🍦"));
+        assert_eq!(0.0, points(&excluded, "🍪"));
+    }
+
+    #[test]
+    fn dowdall_and_custom() {
+        let e = election(&CANDIDATES, vec!((1, vec!(0, 1)), (1, vec!(2, 1))));
+
+        let dowdall = score(&e, Positional::Dowdall, UnrankedScoring::Zero);
+        assert_eq!(1.0, points(&dowdall, "🍪"));
+
+        let custom = score(&e, Positional::Custom(&[5, 3]), UnrankedScoring::Zero);
+        assert_eq!(5.0, points(&custom, "🍦"));
+        assert_eq!(6.0, points(&custom, "🍪"));
+    }
+}
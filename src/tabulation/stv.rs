@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use crate::model::{StvResults, StvRound, WeightedTally};
+
+use super::{Election, TieBreaker};
+
+/// Elects `seats` candidates by single transferable vote.
+///
+/// The quota is the Droop quota of the ballots ranking at least one candidate. Each
+/// round, candidates reaching the quota are elected and their surplus is passed on by
+/// scaling the weight of every ballot they hold (fractional Gregory transfer). When no
+/// one reaches the quota the candidate with the fewest votes is eliminated. Once the
+/// remaining candidates can only just fill the open seats, they are all elected.
+/// Ties for elimination, or for the last seat among candidates over the quota, are
+/// resolved by `tie_breaker`.
+pub fn stv(election: &Election, seats: usize, tie_breaker: &mut TieBreaker) -> StvResults {
+    let valid = election.ballots.iter().filter(|b| !b.is_empty()).count();
+    let quota = (valid / (seats + 1) + 1) as f64;
+
+    let mut weights = vec![1f64; election.ballots.len()];
+    let mut continuing: HashSet<usize> = (0..election.candidates.len()).collect();
+    let mut elected: Vec<usize> = Vec::new();
+    let mut rounds = Vec::new();
+    let mut history: Vec<Vec<f64>> = Vec::new();
+
+    while elected.len() < seats && !continuing.is_empty() {
+        let mut votes = vec![0f64; election.candidates.len()];
+        let mut holder = vec![None; election.ballots.len()];
+        let mut exhausted = 0f64;
+        for (i, ballot) in election.ballots.iter().enumerate() {
+            match ballot.iter().find(|c| continuing.contains(c)) {
+                Some(&c) => {
+                    votes[c] += weights[i];
+                    holder[i] = Some(c);
+                }
+                None => exhausted += weights[i],
+            }
+        }
+
+        let mut standing: Vec<usize> = continuing.iter().copied().collect();
+        standing.sort_by(|&a, &b| votes[b].partial_cmp(&votes[a]).unwrap().then(a.cmp(&b)));
+
+        let tallies = standing.iter()
+            .map(|&c| WeightedTally {
+                candidate: election.name(c),
+                votes: votes[c],
+            })
+            .collect();
+
+        let open_seats = seats - elected.len();
+        let round_elected: Vec<usize> = if standing.len() <= open_seats {
+            standing.clone()
+        } else {
+            let mut over_quota: Vec<usize> = standing.iter()
+                .copied()
+                .filter(|&c| votes[c] >= quota)
+                .collect();
+            if over_quota.len() > open_seats {
+                let cutoff = votes[over_quota[open_seats]];
+                if votes[over_quota[open_seats - 1]] == cutoff {
+                    let tied: Vec<usize> = over_quota.iter()
+                        .copied()
+                        .filter(|&c| votes[c] == cutoff)
+                        .collect();
+                    let resolved = tie_breaker.resolve(election, &tied, &history, Some(rounds.len()));
+                    over_quota.retain(|&c| votes[c] > cutoff);
+                    over_quota.extend(resolved);
+                }
+                over_quota.truncate(open_seats);
+            }
+            over_quota
+        };
+
+        let eliminated = if round_elected.is_empty() {
+            let fewest = votes[*standing.last().expect("candidates remain")];
+            let tied: Vec<usize> = standing.iter()
+                .copied()
+                .filter(|&c| votes[c] == fewest)
+                .collect();
+            let eliminated = if tied.len() > 1 {
+                *tie_breaker.resolve(election, &tied, &history, Some(rounds.len()))
+                    .last()
+                    .expect("tie has candidates")
+            } else {
+                tied[0]
+            };
+            continuing.remove(&eliminated);
+            Some(election.name(eliminated))
+        } else {
+            for &c in round_elected.iter() {
+                continuing.remove(&c);
+                // a candidate elected at exactly the quota has no surplus to pass on
+                let transfer_value = if votes[c] > 0.0 {
+                    (votes[c] - quota).max(0.0) / votes[c]
+                } else {
+                    0.0
+                };
+                for (i, weight) in weights.iter_mut().enumerate() {
+                    if holder[i] == Some(c) {
+                        *weight *= transfer_value;
+                    }
+                }
+            }
+            None
+        };
+
+        elected.extend(round_elected.iter().copied());
+        history.push(votes);
+        rounds.push(StvRound {
+            tallies,
+            exhausted,
+            elected: election.names(&round_elected),
+            eliminated,
+        });
+    }
+
+    StvResults {
+        seats,
+        quota,
+        rounds,
+        elected: election.names(&elected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::TieBreak;
+    use crate::tabulation::election;
+
+    use super::*;
+
+    fn elected(results: &StvResults) -> Vec<&str> {
+        results.elected.iter().map(|c| c.as_str()).collect()
+    }
+
+    #[test]
+    fn transfers_surplus() {
+        // quota is 4; 🍦's surplus of 2 moves 🍪 ahead of 🎂
+        let e = election(&["🍦", "🍪", "🎂"], vec!(
+            (6, vec!(0, 1)),
+            (3, vec!(2)),
+            (2, vec!(1)),
+        ));
+        let results = stv(&e, 2, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(4.0, results.quota);
+        assert_eq!(vec!("🍦", "🍪"), elected(&results));
+        let second = &results.rounds[1];
+        let tally = second.tallies.iter()
+            .find(|t| t.candidate.as_str() == "🍪")
+            .expect("🍪 should be counted");
+        assert!((tally.votes - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exact_quota_has_no_surplus() {
+        // quota is 3; 🍦 is elected with exactly 3, so nothing moves on to 🍪
+        let e = election(&["🍦", "🍪", "🎂", "🍩"], vec!(
+            (3, vec!(0, 1)),
+            (2, vec!(2)),
+            (2, vec!(3)),
+            (1, vec!(1)),
+        ));
+        let results = stv(&e, 2, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(3.0, results.quota);
+        assert_eq!(vec!("🍦"), results.rounds[0].elected.iter().map(|c| c.as_str()).collect::<Vec<_>>());
+        let tally = results.rounds[1].tallies.iter()
+            .find(|t| t.candidate.as_str() == "🍪")
+            .expect("🍪 should be counted");
+        assert!((tally.votes - 1.0).abs() < 1e-9);
+        assert_eq!(Some("🍪"), results.rounds[1].eliminated.as_deref().map(|c| c.as_str()));
+    }
+
+    #[test]
+    fn single_seat_matches_instant_runoff() {
+        let e = election(&["🍦", "🍪", "🎂"], vec!(
+            (2, vec!(0)),
+            (2, vec!(1)),
+            (1, vec!(2, 1)),
+        ));
+        let results = stv(&e, 1, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(vec!("🍪"), elected(&results));
+        assert_eq!(Some("🎂"), results.rounds[0].eliminated.as_deref().map(|c| c.as_str()));
+    }
+
+    #[test]
+    fn fills_seats_with_remaining_candidates() {
+        let e = election(&["🍦", "🍪"], vec!((1, vec!(0))));
+        let results = stv(&e, 2, &mut TieBreaker::new(&e, TieBreak::Random, 0, &[]));
+
+        assert_eq!(vec!("🍦", "🍪"), elected(&results));
+    }
+}
@@ -0,0 +1,146 @@
+use std::cmp::Ordering;
+
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::model::{Tie, TieBreak};
+
+use super::Election;
+
+/// Resolves ties between candidates according to a poll's tie-break rule, keeping a
+/// record of every tie it is asked to resolve.
+///
+/// Every rule falls back to a random order of the candidates drawn from the poll's
+/// seed, so recounting the same ballots always resolves ties the same way.
+pub struct TieBreaker {
+    rule: TieBreak,
+    priority: Vec<usize>,
+    ties: Vec<Tie>,
+}
+
+impl TieBreaker {
+    /// `owner_order` lists candidate names from most to least favoured and is only
+    /// consulted by the `Owner` rule.
+    pub fn new(election: &Election, rule: TieBreak, seed: i64, owner_order: &[String]) -> TieBreaker {
+        let n = election.candidates.len();
+
+        let mut random: Vec<usize> = (0..n).collect();
+        random.shuffle(&mut ChaCha8Rng::seed_from_u64(seed as u64));
+
+        let mut order: Vec<usize> = match rule {
+            TieBreak::Lexicographic => {
+                let mut order: Vec<usize> = (0..n).collect();
+                order.sort_by_key(|&c| &election.candidates[c]);
+                order
+            },
+            TieBreak::Owner => owner_order.iter()
+                .flat_map(|name| election.candidates.iter().position(|c| c.as_str() == name))
+                .collect(),
+            TieBreak::PreviousRounds | TieBreak::Random => vec!(),
+        };
+        order.extend(random.into_iter().filter(|c| !order.contains(c)).collect::<Vec<_>>());
+
+        let mut priority = vec![0; n];
+        for (rank, &c) in order.iter().enumerate() {
+            priority[c] = rank;
+        }
+
+        TieBreaker {
+            rule,
+            priority,
+            ties: Vec::new(),
+        }
+    }
+
+    /// Orders tied candidates from most to least favoured and records the tie.
+    ///
+    /// `history` holds each candidate's count in the rounds before this one; it is
+    /// only used by the `PreviousRounds` rule, which favours the candidate that did
+    /// better in the most recent round where the tied candidates differed.
+    pub fn resolve(&mut self,
+        election: &Election,
+        tied: &[usize],
+        history: &[Vec<f64>],
+        round: Option<usize>,
+    ) -> Vec<usize> {
+        let by_history = |a: usize, b: usize| history.iter()
+            .rev()
+            .map(|counts| counts[b].partial_cmp(&counts[a]).unwrap_or(Ordering::Equal))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal);
+
+        let mut ordered = tied.to_vec();
+        let mut resolution = self.rule;
+        if self.rule == TieBreak::PreviousRounds {
+            ordered.sort_by(|&a, &b| by_history(a, b).then(self.priority[a].cmp(&self.priority[b])));
+            if ordered.windows(2).any(|w| by_history(w[0], w[1]) == Ordering::Equal) {
+                resolution = TieBreak::Random;
+            }
+        } else {
+            ordered.sort_by_key(|&c| self.priority[c]);
+        }
+
+        self.ties.push(Tie {
+            round,
+            candidates: election.names(&ordered),
+            resolution,
+        });
+        ordered
+    }
+
+    /// A fixed order of all candidates for breaking ties that don't arise between
+    /// candidates directly, such as between pairwise victories of equal strength.
+    pub fn priority(&self, candidate: usize) -> usize {
+        self.priority[candidate]
+    }
+
+    pub fn into_ties(self) -> Vec<Tie> {
+        self.ties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tabulation::election;
+
+    use super::*;
+
+    #[test]
+    fn lexicographic() {
+        let e = election(&["🍪", "🍦", "🎂"], vec!());
+        let mut tie_breaker = TieBreaker::new(&e, TieBreak::Lexicographic, 0, &[]);
+
+        assert_eq!(vec!(1, 0, 2), tie_breaker.resolve(&e, &[0, 1, 2], &[], None));
+    }
+
+    #[test]
+    fn owner_order_then_random() {
+        let e = election(&["🍪", "🍦", "🎂"], vec!());
+        let mut tie_breaker = TieBreaker::new(&e, TieBreak::Owner, 0, &["🎂".to_string()]);
+
+        assert_eq!(2, tie_breaker.resolve(&e, &[0, 1, 2], &[], None)[0]);
+    }
+
+    #[test]
+    fn random_is_reproducible() {
+        let e = election(&["🍪", "🍦", "🎂"], vec!());
+        let first = TieBreaker::new(&e, TieBreak::Random, 42, &[]).resolve(&e, &[0, 1, 2], &[], None);
+        let second = TieBreaker::new(&e, TieBreak::Random, 42, &[]).resolve(&e, &[0, 1, 2], &[], None);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn previous_rounds_falls_back_to_random() {
+        let e = election(&["🍪", "🍦", "🎂"], vec!());
+        let mut tie_breaker = TieBreaker::new(&e, TieBreak::PreviousRounds, 0, &[]);
+        let history = vec!(vec!(1.0, 1.0, 3.0), vec!(2.0, 1.0, 1.0));
+
+        assert_eq!(0, tie_breaker.resolve(&e, &[0, 1], &history, Some(2))[0]);
+        tie_breaker.resolve(&e, &[0, 1], &history[..0], Some(0));
+
+        let ties = tie_breaker.into_ties();
+        assert_eq!(TieBreak::PreviousRounds, ties[0].resolution);
+        assert_eq!(TieBreak::Random, ties[1].resolution);
+    }
+}
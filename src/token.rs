@@ -0,0 +1,75 @@
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+
+/// Marks an owner id as belonging to a token's user account rather than a hashed secret.
+pub const TOKEN_OWNER_PREFIX: &str = "token:";
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+/// Validates HS256 bearer tokens issued by the frontend with a shared key. Expiry is
+/// always checked, so tokens can be kept short-lived.
+#[derive(Clone)]
+pub struct TokenValidator {
+    key: Vec<u8>,
+}
+
+impl TokenValidator {
+    pub fn new(key: &[u8]) -> TokenValidator {
+        TokenValidator { key: key.to_vec() }
+    }
+
+    /// Returns the owner id for the token's subject.
+    pub fn validate(&self, token: &str) -> Result<String, jsonwebtoken::errors::Error> {
+        let data = jsonwebtoken::decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(&self.key),
+            &Validation::new(Algorithm::HS256),
+        )?;
+        Ok(format!("{}{}", TOKEN_OWNER_PREFIX, data.claims.sub))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use chrono::{Duration, Utc};
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct TestClaims<'a> {
+        sub: &'a str,
+        exp: i64,
+    }
+
+    pub fn sign(key: &[u8], sub: &str, expires_in: Duration) -> String {
+        let claims = TestClaims { sub, exp: (Utc::now() + expires_in).timestamp() };
+        jsonwebtoken::encode(&Header::default(), &claims, &EncodingKey::from_secret(key))
+            .expect("Failed to sign test token")
+    }
+
+    #[test]
+    fn accepts_valid_token() {
+        let validator = TokenValidator::new(b"key");
+        let token = sign(b"key", "user 1", Duration::minutes(5));
+        assert_eq!("token:user 1", validator.validate(&token).unwrap());
+    }
+
+    #[test]
+    fn rejects_wrong_key() {
+        let validator = TokenValidator::new(b"key");
+        let token = sign(b"other key", "user 1", Duration::minutes(5));
+        assert!(validator.validate(&token).is_err());
+    }
+
+    #[test]
+    fn rejects_expired_token() {
+        let validator = TokenValidator::new(b"key");
+        let token = sign(b"key", "user 1", Duration::minutes(-5));
+        assert!(validator.validate(&token).is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use std::hash::Hash;
 use std::collections::HashSet;
 
-pub fn first_duplicate<'a, A>(iter: impl Iterator<Item=A>) -> Option<A>
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Deserializer};
+
+pub fn first_duplicate<A>(iter: impl Iterator<Item=A>) -> Option<A>
 where A: Eq + Hash {
     let mut set = HashSet::<A>::new();
     for a in iter {
@@ -13,6 +16,35 @@ where A: Eq + Hash {
     None
 }
 
+/// Deserializes a field that may be left out, set to null, or set to a value, as
+/// `None`, `Some(None)` and `Some(Some(value))`. Use with `#[serde(default)]`.
+pub fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where T: Deserialize<'de>, D: Deserializer<'de> {
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// A position in a listing ordered by timestamp, then id. Encoded as
+/// `<microseconds since the epoch>.<id>` so clients can treat it as opaque.
+#[derive(Debug, PartialEq)]
+pub struct Cursor {
+    pub timestamp: DateTime<Utc>,
+    pub id: String,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        format!("{}.{}", self.timestamp.timestamp_nanos() / 1000, self.id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Cursor> {
+        let (micros, id) = cursor.split_once('.')?;
+        let micros: i64 = micros.parse().ok()?;
+        let timestamp = Utc.timestamp_opt(micros.div_euclid(1_000_000), (micros.rem_euclid(1_000_000) * 1000) as u32)
+            .single()?;
+        Some(Cursor { timestamp, id: id.to_string() })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -26,9 +58,33 @@ mod test {
 
     #[test]
     fn finds_dupe() {
-        let empty = vec!("🍦", "🍪", "🍪");
+        let empty = ["🍦", "🍪", "🍪"];
         let result = first_duplicate(empty.iter())
             .expect("Should find duplicate");
         assert_eq!("🍪", *result);
     }
+
+    #[test]
+    fn nullable_tells_null_from_missing() {
+        #[derive(Deserialize)]
+        struct Patch {
+            #[serde(default, deserialize_with = "nullable")]
+            field: Option<Option<u32>>,
+        }
+        let parse = |json| serde_json::from_str::<Patch>(json).expect("should parse").field;
+        assert_eq!(None, parse("{}"));
+        assert_eq!(Some(None), parse(r#"{"field": null}"#));
+        assert_eq!(Some(Some(1)), parse(r#"{"field": 1}"#));
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        let cursor = Cursor {
+            timestamp: Utc.timestamp(1_600_000_000, 123_456_000),
+            id: "abc.def".to_string(),
+        };
+        assert_eq!("1600000000123456.abc.def", cursor.encode());
+        assert_eq!(Some(cursor), Cursor::decode("1600000000123456.abc.def"));
+        assert_eq!(None, Cursor::decode("not a cursor"));
+    }
 }
\ No newline at end of file